@@ -0,0 +1,82 @@
+use std::collections::HashSet;
+use std::error::Error;
+
+use aoc_common::{Answer, Solution};
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    const YEAR: u16 = 2018;
+    const DAY: u8 = 1;
+
+    type Input = Vec<i32>;
+
+    fn parse(input: &str) -> Result<Self::Input, Box<dyn Error>> {
+        from_input(input).map_err(|e| format!("Input line \"{}\" is not a number.", e).into())
+    }
+
+    fn part1(input: &Self::Input) -> Result<Answer, Box<dyn Error>> {
+        Ok(input.iter().sum::<i32>().into())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Answer, Box<dyn Error>> {
+        Ok(find_twice(input).into())
+    }
+}
+
+fn from_input(input: &str) -> Result<Vec<i32>, &str> {
+    let mut ret = Vec::new();
+    for line in input.lines() {
+        ret.push(match line.parse() {
+            Ok(i) => i,
+            Err(_) => return Err(line),
+        });
+    }
+    Ok(ret)
+}
+
+fn find_twice(input: &[i32]) -> i32 {
+    let mut frequencies = HashSet::new();
+    let mut curr_freq = 0;
+
+    frequencies.insert(curr_freq);
+
+    loop {
+        for i in input.iter() {
+            curr_freq += i;
+            if !frequencies.insert(curr_freq) {
+                return curr_freq;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn interpret_input() {
+        let input = "+2\n+4\n-3\n";
+        let ans = from_input(input);
+        assert_eq!(vec![2, 4, -3], ans.unwrap());
+    }
+
+    #[test]
+    fn fail_input() {
+        let invalid_input = "+2\nwrong\n-3\n";
+        assert!(from_input(invalid_input).is_err());
+    }
+
+    #[test]
+    fn twice_half_input() {
+        let input = vec![2, -5, 3, 7, 9];
+        assert_eq!(0, find_twice(&input));
+    }
+
+    #[test]
+    fn twice_loop_input() {
+        let input = vec![1, -2, 3, 1];
+        assert_eq!(2, find_twice(&input));
+    }
+}
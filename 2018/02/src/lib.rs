@@ -0,0 +1,158 @@
+use std::collections::HashMap;
+use std::cmp;
+use std::error::Error;
+
+use aoc_common::{Answer, Solution};
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    const YEAR: u16 = 2018;
+    const DAY: u8 = 2;
+
+    type Input = Vec<String>;
+
+    fn parse(input: &str) -> Result<Self::Input, Box<dyn Error>> {
+        Ok(input.lines().map(String::from).collect())
+    }
+
+    fn part1(ids: &Self::Input) -> Result<Answer, Box<dyn Error>> {
+        let mut two_rep_cnt = 0;
+        let mut three_rep_cnt = 0;
+
+        for id in ids.iter() {
+            let (two_rep, three_rep) = count_repetition(id);
+            if two_rep { two_rep_cnt += 1; }
+            if three_rep { three_rep_cnt += 1; }
+        }
+
+        Ok((two_rep_cnt * three_rep_cnt).into())
+    }
+
+    fn part2(ids: &Self::Input) -> Result<Answer, Box<dyn Error>> {
+        for i in 0..ids.len() {
+            for j in i..ids.len() {
+                if hamming_distance(&ids[i], &ids[j]) == 1 {
+                    return Ok(common_letters(&ids[i], &ids[j]).into());
+                }
+            }
+        }
+
+        Err("No two IDs differ by exactly one char.".into())
+    }
+}
+
+fn count_repetition(id: &str) -> (bool, bool) {
+    let mut letters = HashMap::new();
+
+    for letter in id.chars() {
+        let counter = letters.entry(letter).or_insert(0);
+        *counter += 1;
+    }
+
+    let mut two_rep = false;
+    let mut three_rep = false;
+
+    for count in letters.values() {
+        match count {
+            2 => two_rep = true,
+            3 => three_rep = true,
+            _ => (),
+        }
+    }
+
+    (two_rep, three_rep)
+}
+
+fn hamming_distance(a: &str, b: &str) -> u32 {
+    if a.len() != b.len() {
+        panic!("String lengths differ, they must be the same.");
+    }
+
+    let mut sum = 0;
+
+    for pair in a.chars().zip(b.chars()) {
+        if pair.0 != pair.1 {
+            sum += 1;
+        }
+    }
+
+    sum
+}
+
+fn common_letters(a: &str, b: &str) -> String {
+    let mut r = String::new();
+
+    let m = cmp::min(a.len(), b.len());
+    for i in 0..m {
+        // WARNING: UGLY, UGLY CODE BELOW!
+        if a[i..].chars().next().unwrap() == b[i..].chars().next().unwrap() {
+            r.push(a[i..].chars().next().unwrap());
+        }
+    }
+
+    r
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn count_letter_repetition() {
+        let ids = vec![
+            ("abcdef", (false, false)),
+            ("bababc", (true, true)),
+            ("abbcde", (true, false)),
+            ("abcccd", (false, true)),
+            ("aabcdd", (true, false)),
+            ("abcdee", (true, false)),
+            ("ababab", (false, true))
+        ];
+
+        for (id, rep) in ids {
+            assert_eq!(rep, count_repetition(id));
+        }
+    }
+
+    #[test]
+    fn check_hamming_distance() {
+        let string_pairs = vec![
+            (("abcdef", "abcdef"), 0),
+            (("abcdef", "abcddd"), 2),
+            (("abcdef", "fedcba"), 6)
+        ];
+
+        for (pair, result) in string_pairs {
+            assert_eq!(result, hamming_distance(pair.0, pair.1));
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn check_hamming_panic() {
+        let _ = hamming_distance("big string", "small string");
+    }
+
+    #[test]
+    fn check_common_letters() {
+        let string_pairs = vec![
+            (("abcdef", "abcdef"), "abcdef"),
+            (("abcdef", "abceef"), "abcef"),
+            (("abcbab", "abcaba"), "abc")
+        ];
+
+        for (pair, result) in string_pairs {
+            assert_eq!(result, common_letters(pair.0, pair.1));
+        }
+    }
+
+    #[test]
+    fn solve_sample() {
+        let ids = Day02::parse("abcdef\nbababc\nabbcde\nabcccd\naabcdd\nabcdee\nababab").unwrap();
+        assert_eq!(Answer::from(12), Day02::part1(&ids).unwrap());
+
+        let ids = Day02::parse("abcde\nfghij\nklmno\npqrst\nfguij\naxcye\nwvxyz").unwrap();
+        assert_eq!(Answer::from("fgij"), Day02::part2(&ids).unwrap());
+    }
+}
@@ -0,0 +1,77 @@
+use std::error::Error;
+
+use aoc_common::{Answer, Solution};
+use itertools::Itertools;
+
+/// Sum the entries of the puzzle combinations must be equal to.
+pub const SUM: u32 = 2020;
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    const YEAR: u16 = 2020;
+    const DAY: u8 = 1;
+
+    type Input = Vec<u32>;
+
+    fn parse(input: &str) -> Result<Self::Input, Box<dyn Error>> {
+        Ok(parse_entries(input)?)
+    }
+
+    fn part1(entries: &Self::Input) -> Result<Answer, Box<dyn Error>> {
+        product_of_combination(2, entries)
+    }
+
+    fn part2(entries: &Self::Input) -> Result<Answer, Box<dyn Error>> {
+        product_of_combination(3, entries)
+    }
+}
+
+fn product_of_combination(size: usize, entries: &[u32]) -> Result<Answer, Box<dyn Error>> {
+    let comb = find_combination(size, SUM, entries.to_vec())
+        .ok_or("No combination of entries found that match the given criteria.")?;
+
+    Ok(comb.iter().product::<u32>().into())
+}
+
+pub fn parse_entries(input: &str) -> Result<Vec<u32>, std::num::ParseIntError> {
+    input.lines().map(str::parse).collect()
+}
+
+pub fn find_combination(size: usize, sum: u32, entries: Vec<u32>) -> Option<Vec<u32>> {
+    entries
+        .into_iter()
+        .combinations(size)
+        .find(|v| sum == v.iter().sum())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_entries() {
+        assert_eq!(parse_entries("1721\n979\n").unwrap(), vec![1721, 979]);
+        assert!(parse_entries("1721\nabc\n").is_err());
+    }
+
+    #[test]
+    fn test_find_pair() {
+        let pair = find_combination(2, 2020, vec![2, 10, 1000, 9999, 1020]).expect("a pair");
+        assert_eq!(pair, vec![1000, 1020]);
+        assert_eq!(pair.len(), 2);
+    }
+
+    #[test]
+    fn test_find_pair_fail() {
+        let pair = find_combination(2, 2020, vec![2, 10, 1000, 9999]);
+        assert!(pair.is_none());
+    }
+
+    #[test]
+    fn test_find_trio() {
+        let trio = find_combination(3, 2020, vec![2, 10, 1000, 1010]).expect("a trio");
+        assert_eq!(trio, vec![10, 1000, 1010]);
+        assert_eq!(trio.len(), 3);
+    }
+}
@@ -1,9 +1,11 @@
 use std::io;
 use std::io::Read;
 
-use itertools::Itertools;
 use structopt::StructOpt;
 
+use aoc2001::find_combination;
+use aoc2001::parse_entries;
+
 /// Reads entries from stdin and find a combination of given size that sums to the given value.
 #[derive(Debug, StructOpt)]
 #[structopt(name = "aoc2001")]
@@ -44,39 +46,5 @@ fn get_entries() -> Vec<u32> {
     io::stdin()
         .read_to_string(&mut s)
         .expect("only valid UTF-8 input");
-    s.lines()
-        .map(|s| s.parse().expect("only valid integers"))
-        .collect()
-}
-
-fn find_combination(size: usize, sum: u32, entries: Vec<u32>) -> Option<Vec<u32>> {
-    entries
-        .into_iter()
-        .combinations(size)
-        .find(|v| sum == v.iter().sum())
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_find_pair() {
-        let pair = find_combination(2, 2020, vec![2, 10, 1000, 9999, 1020]).expect("a pair");
-        assert_eq!(pair, vec![1000, 1020]);
-        assert_eq!(pair.len(), 2);
-    }
-
-    #[test]
-    fn test_find_pair_fail() {
-        let pair = find_combination(2, 2020, vec![2, 10, 1000, 9999]);
-        assert!(pair.is_none());
-    }
-
-    #[test]
-    fn test_find_trio() {
-        let trio = find_combination(3, 2020, vec![2, 10, 1000, 1010]).expect("a trio");
-        assert_eq!(trio, vec![10, 1000, 1010]);
-        assert_eq!(trio.len(), 3);
-    }
+    parse_entries(&s).expect("only valid integers")
 }
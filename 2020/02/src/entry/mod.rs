@@ -34,10 +34,10 @@ impl FromStr for Entry {
         let cap = re.captures(s).ok_or("can't parse string")?;
 
         // safe to use unwrap()s, as the regex is already ensuring correct strings
-        let a = (&cap[1]).parse().unwrap();
-        let b = (&cap[2]).parse().unwrap();
-        let letter = (&cap[3]).chars().next().unwrap();
-        let password = (&cap[4]).to_string();
+        let a = cap[1].parse().unwrap();
+        let b = cap[2].parse().unwrap();
+        let letter = cap[3].chars().next().unwrap();
+        let password = cap[4].to_string();
 
         Ok(Self {
             a,
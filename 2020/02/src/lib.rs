@@ -0,0 +1,30 @@
+pub mod entry;
+pub mod policies;
+
+use std::error::Error;
+
+use aoc_common::{Answer, Solution};
+
+use crate::entry::Entries;
+use crate::policies::PolicyType;
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    const YEAR: u16 = 2020;
+    const DAY: u8 = 2;
+
+    type Input = Entries;
+
+    fn parse(input: &str) -> Result<Self::Input, Box<dyn Error>> {
+        Ok(input.parse::<Entries>().map_err(|e| e.error)?)
+    }
+
+    fn part1(entries: &Self::Input) -> Result<Answer, Box<dyn Error>> {
+        Ok(PolicyType::Old.count_compliant(entries).into())
+    }
+
+    fn part2(entries: &Self::Input) -> Result<Answer, Box<dyn Error>> {
+        Ok(PolicyType::New.count_compliant(entries).into())
+    }
+}
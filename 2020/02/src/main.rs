@@ -1,13 +1,10 @@
-mod entry;
-mod policies;
-
 use std::io;
 use std::io::Read;
 
 use structopt::StructOpt;
 
-use crate::entry::Entries;
-use crate::policies::PolicyType;
+use aoc2002::entry::Entries;
+use aoc2002::policies::PolicyType;
 
 /// Reads entries from stdin and checks if passwords comply to the chosen policy.
 #[derive(Debug, StructOpt)]
@@ -25,8 +25,8 @@ impl PolicyType {
         entries
             .iter()
             .filter(match self {
-                Self::New => |e: &&Entry| Self::is_entry_compliant_new(*e),
-                Self::Old => |e: &&Entry| Self::is_entry_compliant_old(*e),
+                Self::New => |e: &&Entry| Self::is_entry_compliant_new(e),
+                Self::Old => |e: &&Entry| Self::is_entry_compliant_old(e),
             })
             .count()
     }
@@ -0,0 +1,85 @@
+mod ordered_list;
+
+use std::error::Error;
+
+use aoc_common::Answer;
+use aoc_common::Solution;
+
+pub use self::ordered_list::OrderedList;
+
+/// Calculates the total distance between two lists of numbers.
+///
+/// The lists must be ordered.
+fn total_distance(list_a: &OrderedList, list_b: &OrderedList) -> i32 {
+    list_a
+        .iter()
+        .zip(list_b.iter())
+        .fold(0, |acc, (a, b)| acc + (a - b).abs())
+}
+
+fn similarity_score(list_a: &[i32], list_b: &[i32]) -> i32 {
+    list_a
+        .iter()
+        .map(|&location_id| {
+            list_b.iter().filter(|&&id| id == location_id).count() as i32 * location_id
+        })
+        .sum()
+}
+
+fn split_and_order_lists(input: &str) -> (OrderedList, OrderedList) {
+    input
+        .lines()
+        .map(|line| {
+            let (a, b) = line.split_once(char::is_whitespace).unwrap();
+            (a.parse::<i32>().unwrap(), b.trim().parse::<i32>().unwrap())
+        })
+        .collect()
+}
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 1;
+
+    type Input = (OrderedList, OrderedList);
+
+    fn parse(input: &str) -> Result<Self::Input, Box<dyn Error>> {
+        Ok(split_and_order_lists(input))
+    }
+
+    fn part1(input: &Self::Input) -> Result<Answer, Box<dyn Error>> {
+        Ok(total_distance(&input.0, &input.1).into())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Answer, Box<dyn Error>> {
+        Ok(similarity_score(&input.0, &input.1).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = include_str!("../sample.txt");
+
+    #[test]
+    fn check_total_distance() {
+        let (list_a, list_b) = split_and_order_lists(SAMPLE);
+        assert_eq!(total_distance(&list_a, &list_b), 11);
+    }
+
+    #[test]
+    fn check_similarity_score() {
+        let (list_a, list_b) = split_and_order_lists(SAMPLE);
+        assert_eq!(similarity_score(&list_a, &list_b), 31);
+    }
+
+    #[test]
+    fn test_solution() {
+        let input = Day01::parse(SAMPLE).unwrap();
+
+        assert_eq!(Day01::part1(&input).unwrap(), Answer::from(11));
+        assert_eq!(Day01::part2(&input).unwrap(), Answer::from(31));
+    }
+}
@@ -1,7 +1,7 @@
 use std::ops::Deref;
 
 #[derive(Debug, Default)]
-pub(crate) struct OrderedList {
+pub struct OrderedList {
     pub(crate) list: Vec<i32>,
 }
 
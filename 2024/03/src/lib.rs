@@ -0,0 +1,113 @@
+use std::error::Error;
+
+use aoc_common::Answer;
+use aoc_common::Solution;
+
+fn read_mul_pairs(input: &str) -> Vec<(usize, usize)> {
+    input
+        .split("mul(")
+        .skip(1)
+        .filter_map(|s| {
+            let (pair, _) = s.split_once(')')?;
+
+            let mut iter = pair.split(',');
+
+            let a = iter.next()?.parse().ok()?;
+            let b = iter.next()?.parse().ok()?;
+
+            iter.next().is_none().then_some((a, b))
+        })
+        .collect()
+}
+
+fn read_enabled_mul_pairs(input: &str) -> Vec<(usize, usize)> {
+    input
+        .split("don't()")
+        .enumerate()
+        .filter_map(|(i, s)| {
+            (i == 0)
+                .then_some(s)
+                .or(s.split_once("do()").map(|(_dont, r#do)| r#do))
+        })
+        .flat_map(read_mul_pairs)
+        .collect()
+}
+
+fn sum_of_multiplication(pairs: &[(usize, usize)]) -> usize {
+    pairs.iter().map(|(a, b)| a * b).sum()
+}
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 3;
+
+    type Input = String;
+
+    fn parse(input: &str) -> Result<Self::Input, Box<dyn Error>> {
+        Ok(input.to_owned())
+    }
+
+    fn part1(input: &Self::Input) -> Result<Answer, Box<dyn Error>> {
+        Ok(sum_of_multiplication(&read_mul_pairs(input)).into())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Answer, Box<dyn Error>> {
+        Ok(sum_of_multiplication(&read_enabled_mul_pairs(input)).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))";
+
+    const SAMPLE_WITH_CONDITIONS: &str =
+        "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
+
+    #[test]
+    fn test_read_mul_pairs() {
+        let pairs = read_mul_pairs(SAMPLE);
+
+        assert_eq!(pairs.len(), 4);
+        assert_eq!(pairs[0], (2, 4));
+        assert_eq!(pairs[1], (5, 5));
+        assert_eq!(pairs[2], (11, 8));
+        assert_eq!(pairs[3], (8, 5));
+    }
+
+    #[test]
+    fn test_sum_of_multiplication() {
+        let pairs = read_mul_pairs(SAMPLE);
+
+        assert_eq!(sum_of_multiplication(&pairs), 161);
+    }
+
+    #[test]
+    fn test_read_enabled_mul_pairs() {
+        let pairs = read_enabled_mul_pairs(SAMPLE_WITH_CONDITIONS);
+
+        assert_eq!(pairs, &[(2, 4), (8, 5)]);
+    }
+
+    #[test]
+    fn test_sum_of_multiplication_of_enabled_pairs() {
+        let pairs = read_enabled_mul_pairs(SAMPLE_WITH_CONDITIONS);
+
+        assert_eq!(sum_of_multiplication(&pairs), 48);
+    }
+
+    #[test]
+    fn test_solution() {
+        assert_eq!(
+            Day03::part1(&Day03::parse(SAMPLE).unwrap()).unwrap(),
+            Answer::from(161)
+        );
+        assert_eq!(
+            Day03::part2(&Day03::parse(SAMPLE_WITH_CONDITIONS).unwrap()).unwrap(),
+            Answer::from(48)
+        );
+    }
+}
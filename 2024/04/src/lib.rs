@@ -0,0 +1,191 @@
+use std::error::Error;
+
+use aoc_common::Answer;
+use aoc_common::Solution;
+use aoc_grid::Colour;
+use aoc_grid::Direction;
+use aoc_grid::GridError;
+use aoc_grid::Image;
+use aoc_grid::Matrix;
+use aoc_grid::Pattern;
+
+fn read_matrix(input: &str) -> Result<Matrix<char>, GridError> {
+    input.parse()
+}
+
+fn count_xmas(matrix: &Matrix<char>) -> usize {
+    matrix.find_words(&["XMAS"], &Direction::ALL).len()
+}
+
+/// Draws the word search, with the letters of each "XMAS" found, its "X" in a different colour.
+pub fn render_xmas(matrix: &Matrix<char>) -> Image {
+    let mut cells = matrix.map(|_| Colour::WHITE);
+
+    for found in matrix.find_words(&["XMAS"], &Direction::ALL) {
+        std::iter::successors(Some(found.start), |&position| {
+            matrix.step(position, found.direction)
+        })
+        .take(found.word.len())
+        .for_each(|position| {
+            cells[position] = match matrix[position] {
+                'X' => Colour::RED,
+                _ => Colour::YELLOW,
+            }
+        });
+    }
+
+    Image::new(cells)
+}
+
+/// Two "MAS" crossing on their "A", each possibly written backwards.
+const X_MAS: &str = "M.S\n.A.\nM.S";
+
+fn count_x_mas(matrix: &Matrix<char>) -> usize {
+    let x_mas = X_MAS
+        .parse::<Pattern>()
+        .expect("pattern is valid")
+        .with_rotations();
+
+    matrix.find_pattern(&x_mas).len()
+}
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 4;
+
+    type Input = Matrix<char>;
+
+    fn parse(input: &str) -> Result<Self::Input, Box<dyn Error>> {
+        Ok(read_matrix(input)?)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Answer, Box<dyn Error>> {
+        Ok(count_xmas(input).into())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Answer, Box<dyn Error>> {
+        Ok(count_x_mas(input).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = include_str!("../sample.txt");
+
+    #[test]
+    fn test_word_is_present() {
+        let matrix = read_matrix("OOOS\nOOAO\nOMOO\nXOOO").unwrap();
+
+        assert_eq!(count_xmas(&matrix), 1);
+    }
+
+    #[test]
+    fn test_not_word_is_present_at_border() {
+        let matrix = read_matrix("SOOO\nAOOO\nMOOO\nOXOO").unwrap();
+
+        assert_eq!(count_xmas(&matrix), 0);
+    }
+
+    #[test]
+    fn test_count_xmas() {
+        let matrix = read_matrix(SAMPLE).unwrap();
+
+        assert_eq!(count_xmas(&matrix), 18);
+    }
+
+    #[test]
+    fn test_is_x_mas() {
+        let x_mases = [
+            "M_M\n_A_\nS_S",
+            "M_S\n_A_\nM_S",
+            "S_S\n_A_\nM_M",
+            "S_M\n_A_\nS_M",
+        ];
+
+        for x_mas in x_mases {
+            let matrix = read_matrix(x_mas).unwrap();
+            assert_eq!(count_x_mas(&matrix), 1, "failed for:\n{}", x_mas);
+        }
+    }
+
+    #[test]
+    fn test_not_is_x_mas() {
+        let matrix = read_matrix("M_S\n_A_\nS_M").unwrap();
+
+        assert_eq!(count_x_mas(&matrix), 0);
+    }
+
+    #[test]
+    fn test_not_is_x_mas_at_border_left() {
+        let matrix = read_matrix("MS_\nA__\nMS_").unwrap();
+
+        assert_eq!(count_x_mas(&matrix), 0);
+    }
+
+    #[test]
+    fn test_not_is_x_mas_at_border_right() {
+        let matrix = read_matrix("_MS\n__A\n_MS").unwrap();
+
+        assert_eq!(count_x_mas(&matrix), 0);
+    }
+
+    #[test]
+    fn test_count_x_mas() {
+        let matrix = read_matrix(SAMPLE).unwrap();
+
+        assert_eq!(count_x_mas(&matrix), 9);
+    }
+
+    #[test]
+    fn test_render_xmas() {
+        let matrix = read_matrix("OOOS\nOOAO\nOMOO\nXOOO").unwrap();
+
+        let cells = render_xmas(&matrix).cells().clone();
+
+        assert_eq!(cells[(3, 0)], Colour::RED);
+        assert_eq!(cells[(2, 1)], Colour::YELLOW);
+        assert_eq!(cells[(0, 3)], Colour::YELLOW);
+        assert_eq!(cells[(0, 0)], Colour::WHITE);
+        assert_eq!(
+            cells.iter().filter(|&&cell| cell != Colour::WHITE).count(),
+            4
+        );
+    }
+
+    #[test]
+    fn test_solution() {
+        let input = Day04::parse(SAMPLE).unwrap();
+
+        assert_eq!(Day04::part1(&input).unwrap(), Answer::from(18));
+        assert_eq!(Day04::part2(&input).unwrap(), Answer::from(9));
+    }
+
+    #[test]
+    fn test_count_xmas_is_invariant_under_transforms() {
+        let matrix = read_matrix(SAMPLE).unwrap();
+
+        let mut rotated = matrix.clone();
+        for _ in 0..4 {
+            rotated = rotated.rotate_90();
+            assert_eq!(count_xmas(&rotated), 18);
+        }
+        assert_eq!(count_xmas(&matrix.transpose()), 18);
+        assert_eq!(count_xmas(&matrix.flip_horizontal()), 18);
+        assert_eq!(count_xmas(&matrix.flip_vertical()), 18);
+    }
+
+    #[test]
+    fn test_count_x_mas_is_invariant_under_rotation() {
+        let matrix = read_matrix(SAMPLE).unwrap();
+
+        let mut rotated = matrix.clone();
+        for _ in 0..4 {
+            rotated = rotated.rotate_90();
+            assert_eq!(count_x_mas(&rotated), 9);
+        }
+    }
+}
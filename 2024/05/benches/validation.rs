@@ -0,0 +1,117 @@
+//! Compares checking each page against all the previous ones with indexing the positions of the
+//! pages, to find whether updates follow the rules (2024, day 5, part 1) on generated updates of
+//! thousands of pages.
+
+use std::hint::black_box;
+
+use aoc2405::PageOrderingRules;
+use criterion::criterion_group;
+use criterion::criterion_main;
+use criterion::BenchmarkId;
+use criterion::Criterion;
+
+const PAGES: usize = 10_000;
+
+/// Rules for each page, towards pages with a greater number.
+const RULES_PER_PAGE: usize = 20;
+
+const UPDATE_SIZES: [usize; 3] = [1_000, 3_000, 5_000];
+
+/// Xorshift generator, for the same input on every run.
+fn random_numbers() -> impl FnMut() -> usize {
+    let mut state = 0x2545_f491_4f6c_dd1d_u64;
+    move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state as usize
+    }
+}
+
+/// Rules that only put pages before pages with a greater number, so increasing page lists are
+/// valid.
+fn generated_rules() -> PageOrderingRules {
+    let mut next_random = random_numbers();
+
+    (0..PAGES - 1)
+        .flat_map(|page| {
+            (0..RULES_PER_PAGE)
+                .map(|_| (page, page + 1 + next_random() % (PAGES - page - 1)))
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+/// Increasing list of distinct pages, valid whatever the rules.
+fn generated_update(size: usize) -> Vec<usize> {
+    let mut next_random = random_numbers();
+    let mut pages: Vec<_> = (0..PAGES).collect();
+
+    // partial Fisher-Yates shuffle to pick the pages
+    for i in 0..size {
+        let j = i + next_random() % (PAGES - i);
+        pages.swap(i, j);
+    }
+    pages.truncate(size);
+    pages.sort_unstable();
+    pages
+}
+
+/// The validation `PageOrderingRules` used before indexing the positions of the pages.
+fn pagelist_is_valid_by_scanning(rules: &PageOrderingRules, pagelist: &[usize]) -> bool {
+    for i in 0..pagelist.len() {
+        let page = pagelist[i];
+        let previous_pages = &pagelist[..i];
+
+        if previous_pages
+            .iter()
+            .any(|&p| rules.contains_rule((page, p)))
+        {
+            return false;
+        }
+    }
+
+    true
+}
+
+fn bench_pagelist_is_valid(c: &mut Criterion) {
+    let rules = generated_rules();
+    let mut group = c.benchmark_group("pagelist_is_valid");
+    group.sample_size(10);
+
+    for size in UPDATE_SIZES {
+        let valid = generated_update(size);
+        // the last page swapped with a page that must come before it: the update is invalid,
+        // but scanning only finds out on its last page
+        let last = *valid.last().unwrap();
+        let before_last = valid
+            .iter()
+            .rposition(|&page| rules.contains_rule((page, last)))
+            .expect("a rule towards the last page");
+        let mut invalid = valid.clone();
+        invalid.swap(before_last, size - 1);
+
+        assert!(rules.pagelist_is_valid(&valid));
+        assert!(pagelist_is_valid_by_scanning(&rules, &valid));
+        assert!(!rules.pagelist_is_valid(&invalid));
+        assert!(!pagelist_is_valid_by_scanning(&rules, &invalid));
+
+        for (name, pagelist) in [("valid", &valid), ("invalid", &invalid)] {
+            group.bench_with_input(
+                BenchmarkId::new(format!("scanning_{name}"), size),
+                pagelist,
+                |b, pagelist| b.iter(|| pagelist_is_valid_by_scanning(&rules, black_box(pagelist))),
+            );
+            group.bench_with_input(
+                BenchmarkId::new(format!("indexed_{name}"), size),
+                pagelist,
+                |b, pagelist| b.iter(|| rules.pagelist_is_valid(black_box(pagelist))),
+            );
+        }
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_pagelist_is_valid);
+criterion_main!(benches);
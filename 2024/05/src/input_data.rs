@@ -1,16 +1,33 @@
 mod page_ordering_rules;
+mod validity_tracker;
 
+use std::error::Error;
+use std::fmt::Display;
 use std::str::FromStr;
 
+pub use self::page_ordering_rules::CycleError;
+pub use self::page_ordering_rules::FixedPagelist;
+pub use self::page_ordering_rules::Orderings;
 pub use self::page_ordering_rules::PageOrderingRules;
+pub use self::page_ordering_rules::Violation;
+pub use self::validity_tracker::ValidityChanges;
+pub use self::validity_tracker::ValidityTracker;
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
 pub struct InputData {
     ordering_rules: PageOrderingRules,
     updates_pagelist: Vec<Vec<usize>>,
 }
 
 impl InputData {
+    pub fn ordering_rules(&self) -> &PageOrderingRules {
+        &self.ordering_rules
+    }
+
+    pub fn updates(&self) -> &[Vec<usize>] {
+        &self.updates_pagelist
+    }
+
     pub fn get_correct_updates(&self) -> Vec<&[usize]> {
         self.updates_pagelist
             .iter()
@@ -19,7 +36,17 @@ impl InputData {
             .collect()
     }
 
-    pub fn fix_incorrect_updates(&self) -> Vec<Vec<usize>> {
+    /// Each update with the rules it breaks, none for correct updates.
+    pub fn violations_by_update(&self) -> impl Iterator<Item = (&[usize], Vec<Violation>)> {
+        self.updates_pagelist.iter().map(|pagelist| {
+            (
+                pagelist.as_slice(),
+                self.ordering_rules.violations(pagelist),
+            )
+        })
+    }
+
+    pub fn fix_incorrect_updates(&self) -> Result<Vec<FixedPagelist>, CycleError> {
         self.updates_pagelist
             .iter()
             .filter(|pagelist| !self.ordering_rules.pagelist_is_valid(pagelist))
@@ -28,33 +55,173 @@ impl InputData {
     }
 }
 
+/// Part of the input a line belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Section {
+    Rules,
+    Updates,
+}
+
+impl Display for Section {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Section::Rules => write!(f, "rules"),
+            Section::Updates => write!(f, "updates"),
+        }
+    }
+}
+
+/// Line numbers start at 1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputDataError {
+    /// Update found among the rules, no blank line separating them.
+    MissingSeparator {
+        line: usize,
+        section: Section,
+        text: String,
+    },
+    /// Blank line among the updates, after the one separating them from the rules.
+    DuplicateSeparator { line: usize, section: Section },
+    /// Rule not made of two pages separated by '|'.
+    MalformedRule {
+        line: usize,
+        section: Section,
+        text: String,
+    },
+    InvalidPage {
+        line: usize,
+        section: Section,
+        text: String,
+        page: String,
+    },
+}
+
+impl Display for InputDataError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputDataError::MissingSeparator {
+                line,
+                section,
+                text,
+            } => write!(
+                f,
+                "Update {text:?} at line {line} ({section}), expected a blank line before it"
+            ),
+            InputDataError::DuplicateSeparator { line, section } => {
+                write!(f, "Unexpected blank line {line} ({section})")
+            }
+            InputDataError::MalformedRule {
+                line,
+                section,
+                text,
+            } => write!(
+                f,
+                "Malformed rule {text:?} at line {line} ({section}), expected \"before|after\""
+            ),
+            InputDataError::InvalidPage {
+                line,
+                section,
+                text,
+                page,
+            } => write!(
+                f,
+                "Invalid page {page:?} in {text:?} at line {line} ({section})"
+            ),
+        }
+    }
+}
+
+impl Error for InputDataError {}
+
+fn is_blank(line: &str) -> bool {
+    line.trim().is_empty()
+}
+
+fn parse_page(
+    page: &str,
+    line: usize,
+    section: Section,
+    text: &str,
+) -> Result<usize, InputDataError> {
+    page.parse().map_err(|_| InputDataError::InvalidPage {
+        line,
+        section,
+        text: text.to_string(),
+        page: page.to_string(),
+    })
+}
+
+fn parse_rule(line: usize, text: &str) -> Result<(usize, usize), InputDataError> {
+    match text.split('|').collect::<Vec<_>>()[..] {
+        [page_a, page_b] => Ok((
+            parse_page(page_a, line, Section::Rules, text)?,
+            parse_page(page_b, line, Section::Rules, text)?,
+        )),
+        _ => Err(InputDataError::MalformedRule {
+            line,
+            section: Section::Rules,
+            text: text.to_string(),
+        }),
+    }
+}
+
+/// Parses the rules, starting at the first line of the input.
+fn parse_rules(lines: &[&str]) -> Result<PageOrderingRules, InputDataError> {
+    lines
+        .iter()
+        .enumerate()
+        .map(|(index, text)| parse_rule(index + 1, text))
+        .collect()
+}
+
+fn parse_update(line: usize, text: &str) -> Result<Vec<usize>, InputDataError> {
+    text.split(',')
+        .map(|page| parse_page(page, line, Section::Updates, text))
+        .collect()
+}
+
+/// Parses the rules, a blank line, then the updates. Blank lines at the end are ignored, and
+/// the blank line may be left out when there are no updates.
 impl FromStr for InputData {
-    type Err = Box<dyn std::error::Error>;
+    type Err = InputDataError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut lines = s.lines();
-
-        let ordering_rules = lines
-            .by_ref()
-            .take_while(|line| !line.is_empty())
-            .map(|line| -> Result<_, Self::Err> {
-                let mut parts = line.split('|');
-                let page_a = parts.next().ok_or("invalid input")?.parse()?;
-                let page_b = parts.next().ok_or("invalid input")?.parse()?;
-
-                parts
-                    .next()
-                    .is_none()
-                    .then_some((page_a, page_b))
-                    .ok_or("invalid input".into())
-            })
-            .collect::<Result<PageOrderingRules, _>>()?;
+        let lines: Vec<_> = s.lines().collect();
+        let end = lines.len() - lines.iter().rev().take_while(|line| is_blank(line)).count();
+        let lines = &lines[..end];
+
+        let Some(separator) = lines.iter().position(|line| is_blank(line)) else {
+            // a line that is not even shaped like a rule is an update missing its separator
+            if let Some(index) = lines.iter().position(|line| !line.contains('|')) {
+                return Err(InputDataError::MissingSeparator {
+                    line: index + 1,
+                    section: Section::Rules,
+                    text: lines[index].to_string(),
+                });
+            }
+            return Ok(InputData {
+                ordering_rules: parse_rules(lines)?,
+                updates_pagelist: Vec::new(),
+            });
+        };
+
+        let (rules, updates) = (&lines[..separator], &lines[separator + 1..]);
 
-        let updates_pagelist = lines
-            .map(|line| {
-                line.split(',')
-                    .map(|part| part.parse())
-                    .collect::<Result<Vec<_>, _>>()
+        let ordering_rules = parse_rules(rules)?;
+
+        let updates_pagelist = updates
+            .iter()
+            .enumerate()
+            .map(|(index, text)| {
+                let line = separator + index + 2;
+                if is_blank(text) {
+                    Err(InputDataError::DuplicateSeparator {
+                        line,
+                        section: Section::Updates,
+                    })
+                } else {
+                    parse_update(line, text)
+                }
             })
             .collect::<Result<Vec<_>, _>>()?;
 
@@ -104,6 +271,19 @@ mod tests {
         assert_eq!(data.get_correct_updates(), vec![&vec![1, 2, 5, 6]]);
     }
 
+    #[test]
+    fn test_violations_by_update() {
+        let data = InputData {
+            ordering_rules: PageOrderingRules::from_iter(vec![(1, 2), (5, 6)]),
+            updates_pagelist: vec![vec![1, 2, 5, 6], vec![2, 1, 6, 5]],
+        };
+        let violations: Vec<_> = data.violations_by_update().collect();
+
+        assert_eq!(violations.len(), 2);
+        assert_eq!(violations[0], (&[1, 2, 5, 6][..], vec![]));
+        assert_eq!(violations[1].1.len(), 2);
+    }
+
     #[test]
     fn test_fix_incorrect_updates() {
         let data = InputData {
@@ -111,6 +291,118 @@ mod tests {
             updates_pagelist: vec![vec![1, 2, 5, 6], vec![2, 6, 5]],
         };
 
-        assert_eq!(data.fix_incorrect_updates(), vec![vec![2, 5, 6]]);
+        let fixed = data.fix_incorrect_updates().unwrap();
+
+        assert_eq!(fixed.len(), 1);
+        assert_eq!(fixed[0].pages, vec![2, 5, 6]);
+        assert!(!fixed[0].unique);
+    }
+
+    #[test]
+    fn test_read_input_errors_are_line_numbered() {
+        assert_eq!(
+            InputData::from_str("1|2\n5|x\n\n1,2"),
+            Err(InputDataError::InvalidPage {
+                line: 2,
+                section: Section::Rules,
+                text: "5|x".to_string(),
+                page: "x".to_string(),
+            })
+        );
+        assert_eq!(
+            InputData::from_str("1|2\n\n1,2\n2,,1"),
+            Err(InputDataError::InvalidPage {
+                line: 4,
+                section: Section::Updates,
+                text: "2,,1".to_string(),
+                page: "".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_read_malformed_rule() {
+        assert_eq!(
+            InputData::from_str("1|2|3\n\n1,2"),
+            Err(InputDataError::MalformedRule {
+                line: 1,
+                section: Section::Rules,
+                text: "1|2|3".to_string(),
+            })
+        );
+        assert_eq!(
+            InputData::from_str("1|2\n12\n\n1,2"),
+            Err(InputDataError::MalformedRule {
+                line: 2,
+                section: Section::Rules,
+                text: "12".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_read_missing_separator() {
+        for input in ["1|2\n1,2\n2,1\n", "1|2\n1,2\n2,1\n\n"] {
+            assert_eq!(
+                InputData::from_str(input),
+                Err(InputDataError::MissingSeparator {
+                    line: 2,
+                    section: Section::Rules,
+                    text: "1,2".to_string(),
+                }),
+                "reading {input:?}"
+            );
+        }
+        assert_eq!(
+            InputData::from_str("1|2\n1|2|3\n12"),
+            Err(InputDataError::MissingSeparator {
+                line: 3,
+                section: Section::Rules,
+                text: "12".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_read_rules_only() {
+        let rules = PageOrderingRules::from_iter(vec![(1, 2), (5, 6)]);
+
+        for input in ["1|2\n5|6", "1|2\n5|6\n", "1|2\n5|6\n\n", "1|2\n5|6\n\n\n"] {
+            let data = InputData::from_str(input).unwrap();
+            assert_eq!(data.ordering_rules, rules, "reading {input:?}");
+            assert!(data.updates_pagelist.is_empty(), "reading {input:?}");
+        }
+        assert_eq!(
+            InputData::from_str(""),
+            Ok(InputData {
+                ordering_rules: PageOrderingRules::new(),
+                updates_pagelist: Vec::new(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_read_empty_update_line() {
+        assert_eq!(
+            InputData::from_str("1|2\n\n1,2\n\n2,1\n"),
+            Err(InputDataError::DuplicateSeparator {
+                line: 4,
+                section: Section::Updates,
+            })
+        );
+        assert_eq!(
+            InputData::from_str("1|2\n\n\n1,2"),
+            Err(InputDataError::DuplicateSeparator {
+                line: 3,
+                section: Section::Updates,
+            })
+        );
+    }
+
+    #[test]
+    fn test_read_input_ignores_trailing_blank_lines() {
+        let data = InputData::from_str("1|2\n\n1,2\n\n").unwrap();
+
+        assert_eq!(data.updates_pagelist, vec![vec![1, 2]]);
     }
 }
@@ -1,7 +1,14 @@
+mod orderings;
+
+use std::collections::BTreeSet;
 use std::collections::HashMap;
 use std::collections::HashSet;
+use std::error::Error;
+use std::fmt::Display;
+
+pub use self::orderings::Orderings;
 
-#[derive(Debug, Default)]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct PageOrderingRules {
     rules: HashMap<usize, HashSet<usize>>,
 }
@@ -11,11 +18,37 @@ impl PageOrderingRules {
         Self::default()
     }
 
-    pub fn add_rule(&mut self, (page_before, page_after): (usize, usize)) {
+    /// Adds the rule, returning whether it is new.
+    ///
+    /// A rule `page|page` orders nothing and is ignored, as if it already existed.
+    pub fn add_rule(&mut self, (page_before, page_after): (usize, usize)) -> bool {
+        if page_before == page_after {
+            return false;
+        }
+
         self.rules
             .entry(page_before)
             .or_default()
-            .insert(page_after);
+            .insert(page_after)
+    }
+
+    /// Removes the rule, returning whether it existed.
+    pub fn remove_rule(&mut self, (page_before, page_after): (usize, usize)) -> bool {
+        let Some(pages_after) = self.rules.get_mut(&page_before) else {
+            return false;
+        };
+        let removed = pages_after.remove(&page_after);
+
+        if pages_after.is_empty() {
+            self.rules.remove(&page_before);
+        }
+        removed
+    }
+
+    pub fn contains_rule(&self, (page_before, page_after): (usize, usize)) -> bool {
+        self.rules
+            .get(&page_before)
+            .is_some_and(|pages_after| pages_after.contains(&page_after))
     }
 
     #[cfg(test)]
@@ -23,46 +56,351 @@ impl PageOrderingRules {
         self.rules.get(&page)
     }
 
+    /// Whether the page list follows every rule, in a time linear in the number of pages and of
+    /// rules for these pages.
     pub fn pagelist_is_valid(&self, pagelist: &[usize]) -> bool {
-        for i in 0..pagelist.len() {
-            let page = pagelist[i];
-            let previous_pages = &pagelist[..i];
+        // first and last index of each page
+        let mut positions: HashMap<usize, (usize, usize)> = HashMap::with_capacity(pagelist.len());
+        for (index, &page) in pagelist.iter().enumerate() {
+            positions
+                .entry(page)
+                .and_modify(|(_, last)| *last = index)
+                .or_insert((index, index));
+        }
 
-            if let Some(page_rules) = self.rules.get(&page) {
-                if previous_pages.iter().any(|p| page_rules.contains(p)) {
-                    return false;
+        positions.iter().all(|(page, &(_, last_before))| {
+            self.rules.get(page).is_none_or(|pages_after| {
+                pages_after.iter().all(|page_after| {
+                    positions
+                        .get(page_after)
+                        .is_none_or(|&(first_after, _)| first_after > last_before)
+                })
+            })
+        })
+    }
+
+    /// Pages that appear in at least one rule, in increasing order.
+    pub fn pages(&self) -> BTreeSet<usize> {
+        self.rules
+            .iter()
+            .flat_map(|(&page, pages_after)| {
+                std::iter::once(page).chain(pages_after.iter().copied())
+            })
+            .collect()
+    }
+
+    /// Rules `before|after` in increasing order.
+    pub fn rules(&self) -> Vec<(usize, usize)> {
+        let mut rules: Vec<_> = self
+            .rules
+            .iter()
+            .flat_map(|(&before, pages_after)| {
+                pages_after.iter().map(move |&after| (before, after))
+            })
+            .collect();
+        rules.sort_unstable();
+        rules
+    }
+
+    /// Only the rules between the given pages, as they apply to an update made of these pages.
+    pub fn restricted_to(&self, pages: &[usize]) -> PageOrderingRules {
+        self.rules()
+            .into_iter()
+            .filter(|(before, after)| pages.contains(before) && pages.contains(after))
+            .collect()
+    }
+
+    /// Whether the rules require `before` to come before `after`, directly or through other
+    /// pages.
+    pub fn must_precede(&self, before: usize, after: usize) -> bool {
+        self.reaches(before, after, None)
+    }
+
+    /// Whether `to` can be reached from `from` by following at least one rule, ignoring the rule
+    /// `skipped`.
+    fn reaches(&self, from: usize, to: usize, skipped: Option<(usize, usize)>) -> bool {
+        let mut visited = HashSet::new();
+        let mut stack = vec![from];
+
+        while let Some(page) = stack.pop() {
+            for &next in self.rules.get(&page).into_iter().flatten() {
+                if skipped == Some((page, next)) || !visited.insert(next) {
+                    continue;
                 }
+                if next == to {
+                    return true;
+                }
+                stack.push(next);
             }
         }
 
-        true
+        false
     }
 
-    pub fn fix_pagelist(&self, pagelist: &[usize]) -> Vec<usize> {
-        let mut pagelist = pagelist.to_owned();
+    /// Rules implied by the others, in increasing order.
+    ///
+    /// When rules form a cycle, rules of the cycle can be implied by each other, and removing
+    /// all of them at once changes the order.
+    pub fn redundant_rules(&self) -> Vec<(usize, usize)> {
+        self.rules()
+            .into_iter()
+            .filter(|&(before, after)| self.reaches(before, after, Some((before, after))))
+            .collect()
+    }
 
-        // iterate by index because pagelist must be muted inside the loops
-        for i in 0..pagelist.len() {
-            let page = pagelist[i];
+    /// Groups of pages that the rules order in a cycle, each page having to come before all the
+    /// others of its group, with single pages for the pages outside cycles.
+    ///
+    /// Pages are in increasing order in each group, and groups are sorted by their first page.
+    pub fn strongly_connected_components(&self) -> Vec<Vec<usize>> {
+        let mut tarjan = Tarjan {
+            rules: self,
+            index: HashMap::new(),
+            lowlink: HashMap::new(),
+            stack: Vec::new(),
+            on_stack: HashSet::new(),
+            components: Vec::new(),
+        };
+
+        for page in self.pages() {
+            if !tarjan.index.contains_key(&page) {
+                tarjan.visit(page);
+            }
+        }
+
+        let mut components = tarjan.components;
+        for component in &mut components {
+            component.sort_unstable();
+        }
+        components.sort_unstable();
+        components
+    }
+
+    /// Graphviz description of the rules, one edge per rule, restricted to the given pages if
+    /// any.
+    pub fn to_dot(&self, pages: Option<&[usize]>) -> String {
+        let mut dot = String::from("digraph rules {\n");
 
-            if let Some(page_rules) = self.rules.get(&page) {
-                // look for the first previous_page that should be after page
-                for j in 0..i {
-                    let previous_page = pagelist[j];
+        let rules = match pages {
+            Some(pages) => {
+                for page in pages {
+                    dot.push_str(&format!("    {page};\n"));
+                }
+                self.restricted_to(pages).rules()
+            }
+            None => self.rules(),
+        };
+
+        for (before, after) in rules {
+            dot.push_str(&format!("    {before} -> {after};\n"));
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
 
-                    if page_rules.contains(&previous_page) {
-                        pagelist.copy_within(j..i, j + 1);
-                        pagelist[j] = page;
-                        break;
+    /// Every rule broken by the page list, in the order of the pages that should come first.
+    pub fn violations(&self, pagelist: &[usize]) -> Vec<Violation> {
+        let mut violations = Vec::new();
+
+        for (before_index, &before) in pagelist.iter().enumerate() {
+            if let Some(page_rules) = self.rules.get(&before) {
+                for (after_index, &after) in pagelist[..before_index].iter().enumerate() {
+                    if page_rules.contains(&after) {
+                        violations.push(Violation {
+                            before,
+                            after,
+                            before_index,
+                            after_index,
+                        });
                     }
                 }
             }
         }
 
+        violations
+    }
+
+    /// Graph of the rules between the pages of the list: the indexes of the pages that must come
+    /// after each page, pages being identified by their index in the list.
+    fn successors(&self, pagelist: &[usize]) -> Vec<Vec<usize>> {
         pagelist
+            .iter()
+            .map(|page| match self.rules.get(page) {
+                Some(page_rules) => (0..pagelist.len())
+                    .filter(|&j| page_rules.contains(&pagelist[j]))
+                    .collect(),
+                None => Vec::new(),
+            })
+            .collect()
+    }
+
+    /// Orders the pages of the list so that it follows the rules, keeping pages that the rules
+    /// don't order in their original order.
+    pub fn fix_pagelist(&self, pagelist: &[usize]) -> Result<FixedPagelist, CycleError> {
+        let successors = self.successors(pagelist);
+
+        let mut predecessors_left = vec![0; pagelist.len()];
+        for &j in successors.iter().flatten() {
+            predecessors_left[j] += 1;
+        }
+
+        let mut ready: BTreeSet<usize> = (0..pagelist.len())
+            .filter(|&i| predecessors_left[i] == 0)
+            .collect();
+        let mut pages = Vec::with_capacity(pagelist.len());
+        let mut unique = true;
+
+        while let Some(i) = ready.pop_first() {
+            // another page could have been put here instead
+            unique &= ready.is_empty();
+            pages.push(pagelist[i]);
+
+            for &j in &successors[i] {
+                predecessors_left[j] -= 1;
+                if predecessors_left[j] == 0 {
+                    ready.insert(j);
+                }
+            }
+        }
+
+        if pages.len() < pagelist.len() {
+            return Err(CycleError {
+                pages: find_cycle(pagelist, &successors, &predecessors_left),
+            });
+        }
+
+        Ok(FixedPagelist { pages, unique })
     }
 }
 
+/// State of Tarjan's strongly connected components algorithm.
+struct Tarjan<'r> {
+    rules: &'r PageOrderingRules,
+    index: HashMap<usize, usize>,
+    lowlink: HashMap<usize, usize>,
+    stack: Vec<usize>,
+    on_stack: HashSet<usize>,
+    components: Vec<Vec<usize>>,
+}
+
+impl Tarjan<'_> {
+    fn visit(&mut self, page: usize) {
+        let index = self.index.len();
+        self.index.insert(page, index);
+        self.lowlink.insert(page, index);
+        self.stack.push(page);
+        self.on_stack.insert(page);
+
+        for &next in self.rules.rules.get(&page).into_iter().flatten() {
+            if !self.index.contains_key(&next) {
+                self.visit(next);
+                let lowlink = self.lowlink[&page].min(self.lowlink[&next]);
+                self.lowlink.insert(page, lowlink);
+            } else if self.on_stack.contains(&next) {
+                let lowlink = self.lowlink[&page].min(self.index[&next]);
+                self.lowlink.insert(page, lowlink);
+            }
+        }
+
+        if self.lowlink[&page] == self.index[&page] {
+            let mut component = Vec::new();
+            loop {
+                let member = self.stack.pop().unwrap();
+                self.on_stack.remove(&member);
+                component.push(member);
+                if member == page {
+                    break;
+                }
+            }
+            self.components.push(component);
+        }
+    }
+}
+
+/// Finds a cycle among the pages the topological sort could not place, which all have a
+/// predecessor left.
+fn find_cycle(
+    pagelist: &[usize],
+    successors: &[Vec<usize>],
+    predecessors_left: &[usize],
+) -> Vec<usize> {
+    let left = |i: &usize| predecessors_left[*i] > 0;
+    let mut path = Vec::new();
+    let mut i = (0..pagelist.len()).find(left).unwrap();
+
+    // walk back through predecessors left until coming back to a page of the path
+    while !path.contains(&i) {
+        path.push(i);
+        i = (0..pagelist.len())
+            .filter(left)
+            .find(|&p| successors[p].contains(&i))
+            .unwrap();
+    }
+
+    let cycle_start = path.iter().position(|&p| p == i).unwrap();
+    path[cycle_start..]
+        .iter()
+        .rev()
+        .map(|&p| pagelist[p])
+        .collect()
+}
+
+/// A rule `before|after` broken by a page list, where `after` comes first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Violation {
+    pub before: usize,
+    pub after: usize,
+    /// Index of `before` in the page list.
+    pub before_index: usize,
+    /// Index of `after` in the page list, lower than `before_index`.
+    pub after_index: usize,
+}
+
+impl Display for Violation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}|{} broken: {} at index {} comes before {} at index {}",
+            self.before, self.after, self.after, self.after_index, self.before, self.before_index
+        )
+    }
+}
+
+/// A page list reordered to follow the rules.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FixedPagelist {
+    pub pages: Vec<usize>,
+    /// Whether the rules allow no other order for these pages.
+    pub unique: bool,
+}
+
+impl AsRef<[usize]> for FixedPagelist {
+    fn as_ref(&self) -> &[usize] {
+        &self.pages
+    }
+}
+
+/// Rules that contradict each other: each page of the cycle must come before the next one, and
+/// the last one before the first one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CycleError {
+    pub pages: Vec<usize>,
+}
+
+impl Display for CycleError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Conflicting rules:")?;
+        for (page, next) in self.pages.iter().zip(self.pages.iter().cycle().skip(1)) {
+            write!(f, " {page}|{next}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Error for CycleError {}
+
 impl FromIterator<(usize, usize)> for PageOrderingRules {
     fn from_iter<T: IntoIterator<Item = (usize, usize)>>(iter: T) -> Self {
         let mut rules = PageOrderingRules::new();
@@ -77,6 +415,26 @@ impl FromIterator<(usize, usize)> for PageOrderingRules {
 mod tests {
     use super::*;
 
+    /// Reference implementation of [`PageOrderingRules::pagelist_is_valid`], checking each page
+    /// against all the previous ones.
+    fn pagelist_is_valid_by_scanning(
+        ordering_rules: &PageOrderingRules,
+        pagelist: &[usize],
+    ) -> bool {
+        for i in 0..pagelist.len() {
+            let page = pagelist[i];
+            let previous_pages = &pagelist[..i];
+
+            if let Some(page_rules) = ordering_rules.get_rules_for_page(page) {
+                if previous_pages.iter().any(|p| page_rules.contains(p)) {
+                    return false;
+                }
+            }
+        }
+
+        true
+    }
+
     #[test]
     fn case_no_related_rules_means_any_order_is_acceptable() {
         let ordering_rules = PageOrderingRules::from_iter(vec![(1, 2), (5, 6)]);
@@ -113,11 +471,215 @@ mod tests {
         assert!(!ordering_rules.pagelist_is_valid(&pagelist));
     }
 
+    #[test]
+    fn test_add_and_remove_rule() {
+        let mut ordering_rules = PageOrderingRules::from_iter(vec![(1, 2)]);
+
+        assert!(!ordering_rules.add_rule((1, 2)));
+        assert!(ordering_rules.add_rule((1, 3)));
+        assert!(ordering_rules.contains_rule((1, 3)));
+        assert!(ordering_rules.remove_rule((1, 2)));
+        assert!(!ordering_rules.remove_rule((1, 2)));
+        assert!(!ordering_rules.remove_rule((5, 6)));
+        assert!(ordering_rules.remove_rule((1, 3)));
+        assert_eq!(ordering_rules, PageOrderingRules::new());
+    }
+
+    #[test]
+    fn test_must_precede() {
+        let ordering_rules = PageOrderingRules::from_iter(vec![(1, 2), (2, 3), (5, 6)]);
+
+        assert!(ordering_rules.must_precede(1, 2));
+        assert!(ordering_rules.must_precede(1, 3));
+        assert!(!ordering_rules.must_precede(3, 1));
+        assert!(!ordering_rules.must_precede(1, 5));
+        assert!(!ordering_rules.must_precede(1, 1));
+        assert!(!ordering_rules.restricted_to(&[1, 3]).must_precede(1, 3));
+    }
+
+    #[test]
+    fn test_redundant_rules() {
+        let ordering_rules = PageOrderingRules::from_iter(vec![(1, 2), (2, 3), (1, 3), (3, 4)]);
+
+        assert_eq!(ordering_rules.redundant_rules(), vec![(1, 3)]);
+        assert_eq!(
+            ordering_rules.restricted_to(&[1, 2, 3]).rules(),
+            vec![(1, 2), (1, 3), (2, 3)]
+        );
+    }
+
+    #[test]
+    fn test_strongly_connected_components() {
+        let ordering_rules =
+            PageOrderingRules::from_iter(vec![(1, 2), (2, 3), (3, 1), (3, 4), (4, 5), (5, 4)]);
+
+        assert_eq!(
+            ordering_rules.strongly_connected_components(),
+            vec![vec![1, 2, 3], vec![4, 5]]
+        );
+        assert_eq!(
+            PageOrderingRules::from_iter(vec![(1, 2)]).strongly_connected_components(),
+            vec![vec![1], vec![2]]
+        );
+    }
+
+    #[test]
+    fn test_to_dot() {
+        let ordering_rules = PageOrderingRules::from_iter(vec![(2, 3), (1, 2), (5, 6)]);
+
+        assert_eq!(
+            ordering_rules.to_dot(None),
+            "digraph rules {\n    1 -> 2;\n    2 -> 3;\n    5 -> 6;\n}\n"
+        );
+        assert_eq!(
+            ordering_rules.to_dot(Some(&[3, 2, 4])),
+            "digraph rules {\n    3;\n    2;\n    4;\n    2 -> 3;\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_pagelist_is_valid_matches_scanning() {
+        let ordering_rules =
+            PageOrderingRules::from_iter(vec![(1, 2), (2, 3), (1, 3), (5, 6), (4, 4), (7, 1)]);
+        let pagelists = [
+            vec![],
+            vec![1],
+            vec![1, 2, 3],
+            vec![3, 2, 1],
+            vec![1, 3, 2],
+            vec![1, 2, 1],
+            vec![4],
+            vec![4, 1, 4],
+            vec![6, 8, 9, 5],
+            vec![7, 1, 2, 5, 6, 3],
+            vec![1, 7],
+        ];
+
+        for pagelist in pagelists {
+            assert_eq!(
+                ordering_rules.pagelist_is_valid(&pagelist),
+                pagelist_is_valid_by_scanning(&ordering_rules, &pagelist),
+                "testing pagelist {pagelist:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_violations() {
+        let ordering_rules = PageOrderingRules::from_iter(vec![(1, 2), (5, 6), (1, 6)]);
+
+        assert_eq!(ordering_rules.violations(&[1, 2, 5, 6]), vec![]);
+        assert_eq!(
+            ordering_rules.violations(&[6, 2, 1, 5]),
+            vec![
+                Violation {
+                    before: 1,
+                    after: 6,
+                    before_index: 2,
+                    after_index: 0
+                },
+                Violation {
+                    before: 1,
+                    after: 2,
+                    before_index: 2,
+                    after_index: 1
+                },
+                Violation {
+                    before: 5,
+                    after: 6,
+                    before_index: 3,
+                    after_index: 0
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_violation_display() {
+        let violation = Violation {
+            before: 97,
+            after: 75,
+            before_index: 1,
+            after_index: 0,
+        };
+
+        assert_eq!(
+            violation.to_string(),
+            "97|75 broken: 75 at index 0 comes before 97 at index 1"
+        );
+    }
+
     #[test]
     fn test_fix_pagelist() {
         let ordering_rules = PageOrderingRules::from_iter(vec![(1, 2), (5, 6)]);
         let pagelist = vec![2, 6, 1, 5];
 
-        assert_eq!(ordering_rules.fix_pagelist(&pagelist), vec![1, 2, 5, 6]);
+        assert_eq!(
+            ordering_rules.fix_pagelist(&pagelist),
+            Ok(FixedPagelist {
+                pages: vec![1, 2, 5, 6],
+                unique: false
+            })
+        );
+    }
+
+    #[test]
+    fn test_fix_pagelist_unique_order() {
+        let ordering_rules = PageOrderingRules::from_iter(vec![(1, 2), (2, 3), (1, 3)]);
+        let pagelist = vec![3, 1, 2];
+
+        assert_eq!(
+            ordering_rules.fix_pagelist(&pagelist),
+            Ok(FixedPagelist {
+                pages: vec![1, 2, 3],
+                unique: true
+            })
+        );
+    }
+
+    #[test]
+    fn test_fix_pagelist_ignores_rules_for_other_pages() {
+        let ordering_rules = PageOrderingRules::from_iter(vec![(1, 2), (2, 3), (3, 1)]);
+        let pagelist = vec![2, 1];
+
+        assert_eq!(
+            ordering_rules.fix_pagelist(&pagelist).unwrap().pages,
+            vec![1, 2]
+        );
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_fix_pagelist_cycle() {
+        let ordering_rules = PageOrderingRules::from_iter(vec![(1, 2), (2, 3), (3, 1), (4, 1)]);
+        let error = ordering_rules.fix_pagelist(&[4, 3, 2, 1]).unwrap_err();
+
+        let mut cycle = error.pages.clone();
+        let first = cycle.iter().position(|&page| page == 1).unwrap();
+        cycle.rotate_left(first);
+        assert_eq!(cycle, vec![1, 2, 3]);
+        assert_eq!(
+            CycleError {
+                pages: vec![1, 2, 3]
+            }
+            .to_string(),
+            "Conflicting rules: 1|2 2|3 3|1"
+        );
+    }
+
+    #[test]
+    fn test_fix_pagelist_self_rule() {
+        let mut ordering_rules = PageOrderingRules::from_iter(vec![(1, 1)]);
+
+        assert!(!ordering_rules.add_rule((1, 1)));
+        assert!(!ordering_rules.contains_rule((1, 1)));
+        assert!(ordering_rules.pagelist_is_valid(&[2, 1]));
+        assert!(ordering_rules.pagelist_is_valid(&[1, 2, 1]));
+        assert_eq!(
+            ordering_rules.fix_pagelist(&[2, 1]),
+            Ok(FixedPagelist {
+                pages: vec![2, 1],
+                unique: false,
+            })
+        );
+    }
+}
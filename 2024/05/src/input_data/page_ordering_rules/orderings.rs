@@ -0,0 +1,290 @@
+use std::collections::HashMap;
+
+use super::PageOrderingRules;
+
+impl PageOrderingRules {
+    /// Number of orders of the pages of the list that follow the rules, saturating at
+    /// `u128::MAX`, or `None` if the list has more than 128 pages.
+    ///
+    /// Takes a time and memory proportional to the number of sets of pages that can start a
+    /// valid order. That is small when the rules order most pages, but grows as `2^k` with the
+    /// number `k` of pages the rules leave unordered between them.
+    pub fn count_orderings(&self, pagelist: &[usize]) -> Option<u128> {
+        // sets of pages are bit masks
+        if pagelist.len() > u128::BITS as usize {
+            return None;
+        }
+
+        let mut predecessors = vec![0u128; pagelist.len()];
+        for (i, successors) in self.successors(pagelist).iter().enumerate() {
+            for &j in successors {
+                predecessors[j] |= 1 << i;
+            }
+        }
+
+        Some(count_from(&predecessors, 0, &mut HashMap::new()))
+    }
+
+    /// Lazily enumerates the orders of the pages of the list that follow the rules, use
+    /// [`Iterator::take`] to stop after a given number of them.
+    pub fn orderings(&self, pagelist: &[usize]) -> Orderings {
+        Orderings::new(pagelist, self.successors(pagelist))
+    }
+
+    /// Whether the rules allow exactly one order of the pages of the list.
+    pub fn determines_order(&self, pagelist: &[usize]) -> bool {
+        self.fix_pagelist(pagelist).is_ok_and(|fixed| fixed.unique)
+    }
+}
+
+/// Number of ways to place the pages not in `placed`, a set of page indexes, after them.
+fn count_from(predecessors: &[u128], placed: u128, counts: &mut HashMap<u128, u128>) -> u128 {
+    let all = if predecessors.len() == u128::BITS as usize {
+        u128::MAX
+    } else {
+        (1 << predecessors.len()) - 1
+    };
+    if placed == all {
+        return 1;
+    }
+    if let Some(&count) = counts.get(&placed) {
+        return count;
+    }
+
+    let count = (0..predecessors.len())
+        .filter(|&i| placed & (1 << i) == 0 && predecessors[i] & !placed == 0)
+        .fold(0u128, |count, i| {
+            count.saturating_add(count_from(predecessors, placed | (1 << i), counts))
+        });
+
+    counts.insert(placed, count);
+    count
+}
+
+/// Iterator over the orders of a page list that follow the rules, see
+/// [`PageOrderingRules::orderings`].
+///
+/// Orders come in lexicographic order of the positions of the pages in the original list, so
+/// the first one is the order given by [`PageOrderingRules::fix_pagelist`].
+#[derive(Debug)]
+pub struct Orderings {
+    pagelist: Vec<usize>,
+    successors: Vec<Vec<usize>>,
+    predecessors_left: Vec<usize>,
+    /// Indexes of the pages placed so far.
+    placed: Vec<usize>,
+    is_placed: Vec<bool>,
+    /// Pages that could be placed at each position so far, and how many were tried.
+    frames: Vec<(Vec<usize>, usize)>,
+    /// Whether the only order of an empty list is still to be returned.
+    empty_pending: bool,
+}
+
+impl Orderings {
+    fn new(pagelist: &[usize], successors: Vec<Vec<usize>>) -> Self {
+        let mut predecessors_left = vec![0; pagelist.len()];
+        for &j in successors.iter().flatten() {
+            predecessors_left[j] += 1;
+        }
+
+        // with a cycle no order follows the rules, and some page is always ready otherwise
+        let acyclic = is_acyclic(&successors, predecessors_left.clone());
+
+        let mut orderings = Orderings {
+            pagelist: pagelist.to_vec(),
+            successors,
+            predecessors_left,
+            placed: Vec::with_capacity(pagelist.len()),
+            is_placed: vec![false; pagelist.len()],
+            frames: Vec::new(),
+            empty_pending: pagelist.is_empty(),
+        };
+        if !pagelist.is_empty() && acyclic {
+            let ready = orderings.ready();
+            orderings.frames.push((ready, 0));
+        }
+        orderings
+    }
+
+    fn ready(&self) -> Vec<usize> {
+        (0..self.pagelist.len())
+            .filter(|&i| !self.is_placed[i] && self.predecessors_left[i] == 0)
+            .collect()
+    }
+
+    fn place(&mut self, i: usize) {
+        self.placed.push(i);
+        self.is_placed[i] = true;
+        for &j in &self.successors[i] {
+            self.predecessors_left[j] -= 1;
+        }
+    }
+
+    fn unplace(&mut self) {
+        let i = self.placed.pop().unwrap();
+        self.is_placed[i] = false;
+        for &j in &self.successors[i] {
+            self.predecessors_left[j] += 1;
+        }
+    }
+}
+
+impl Iterator for Orderings {
+    type Item = Vec<usize>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.empty_pending {
+            self.empty_pending = false;
+            return Some(Vec::new());
+        }
+
+        loop {
+            let (candidates, tried) = self.frames.last_mut()?;
+            let candidate = candidates.get(*tried).copied();
+            let backtrack = *tried > 0;
+            *tried += 1;
+
+            // take back the page tried at this position before the next one
+            if backtrack {
+                self.unplace();
+            }
+
+            let Some(i) = candidate else {
+                self.frames.pop();
+                continue;
+            };
+
+            self.place(i);
+            if self.placed.len() == self.pagelist.len() {
+                return Some(self.placed.iter().map(|&i| self.pagelist[i]).collect());
+            }
+
+            let ready = self.ready();
+            self.frames.push((ready, 0));
+        }
+    }
+}
+
+/// Whether the pages can all be placed, taking each one once all its predecessors are placed.
+fn is_acyclic(successors: &[Vec<usize>], mut predecessors_left: Vec<usize>) -> bool {
+    let mut ready: Vec<usize> = (0..successors.len())
+        .filter(|&i| predecessors_left[i] == 0)
+        .collect();
+    let mut placed = 0;
+
+    while let Some(i) = ready.pop() {
+        placed += 1;
+        for &j in &successors[i] {
+            predecessors_left[j] -= 1;
+            if predecessors_left[j] == 0 {
+                ready.push(j);
+            }
+        }
+    }
+
+    placed == successors.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_orderings() {
+        let ordering_rules = PageOrderingRules::from_iter(vec![(1, 2), (5, 6)]);
+        let orderings: Vec<_> = ordering_rules.orderings(&[2, 6, 1, 5]).collect();
+
+        assert_eq!(
+            orderings,
+            vec![
+                vec![1, 2, 5, 6],
+                vec![1, 5, 2, 6],
+                vec![1, 5, 6, 2],
+                vec![5, 6, 1, 2],
+                vec![5, 1, 2, 6],
+                vec![5, 1, 6, 2],
+            ]
+        );
+        assert_eq!(ordering_rules.count_orderings(&[2, 6, 1, 5]), Some(6));
+        assert!(!ordering_rules.determines_order(&[2, 6, 1, 5]));
+    }
+
+    #[test]
+    fn test_orderings_are_lazy() {
+        let ordering_rules = PageOrderingRules::new();
+        let pagelist: Vec<_> = (0..12).collect();
+
+        assert_eq!(ordering_rules.orderings(&pagelist).take(3).count(), 3);
+        assert_eq!(
+            ordering_rules.count_orderings(&pagelist),
+            Some((1..=12).product::<u128>())
+        );
+    }
+
+    #[test]
+    fn test_unique_ordering() {
+        let ordering_rules = PageOrderingRules::from_iter(vec![(1, 2), (2, 3), (1, 3)]);
+
+        assert_eq!(
+            ordering_rules.orderings(&[3, 1, 2]).collect::<Vec<_>>(),
+            vec![vec![1, 2, 3]]
+        );
+        assert_eq!(ordering_rules.count_orderings(&[3, 1, 2]), Some(1));
+        assert!(ordering_rules.determines_order(&[3, 1, 2]));
+    }
+
+    #[test]
+    fn test_orderings_with_cycle() {
+        let ordering_rules = PageOrderingRules::from_iter(vec![(1, 2), (2, 3), (3, 1)]);
+
+        assert_eq!(ordering_rules.orderings(&[4, 3, 2, 1]).next(), None);
+        // the pages outside the cycle are not tried in every order before giving up
+        let pagelist: Vec<_> = (1..=30).collect();
+        assert_eq!(ordering_rules.orderings(&pagelist).next(), None);
+        assert_eq!(ordering_rules.count_orderings(&[4, 3, 2, 1]), Some(0));
+        assert!(!ordering_rules.determines_order(&[4, 3, 2, 1]));
+    }
+
+    #[test]
+    fn test_orderings_of_long_list() {
+        let pagelist: Vec<_> = (0..129).collect();
+        let ordering_rules =
+            PageOrderingRules::from_iter(pagelist.windows(2).map(|pair| (pair[0], pair[1])));
+
+        assert_eq!(ordering_rules.count_orderings(&pagelist), None);
+        assert_eq!(ordering_rules.count_orderings(&pagelist[..128]), Some(1));
+        assert_eq!(
+            ordering_rules.orderings(&pagelist).collect::<Vec<_>>(),
+            vec![pagelist.clone()]
+        );
+        assert!(ordering_rules.determines_order(&pagelist));
+    }
+
+    #[test]
+    fn test_orderings_of_empty_list() {
+        let ordering_rules = PageOrderingRules::new();
+
+        assert_eq!(ordering_rules.orderings(&[]).count(), 1);
+        assert_eq!(ordering_rules.count_orderings(&[]), Some(1));
+    }
+
+    #[test]
+    fn test_first_ordering_is_fixed_pagelist() {
+        let ordering_rules = PageOrderingRules::from_iter(vec![
+            (47, 53),
+            (97, 13),
+            (97, 61),
+            (75, 13),
+            (61, 13),
+            (29, 13),
+            (75, 29),
+            (47, 29),
+        ]);
+        let pagelist = [97, 13, 75, 29, 47];
+
+        assert_eq!(
+            ordering_rules.orderings(&pagelist).next().unwrap(),
+            ordering_rules.fix_pagelist(&pagelist).unwrap().pages
+        );
+    }
+}
@@ -0,0 +1,237 @@
+use std::collections::HashMap;
+
+use super::InputData;
+use super::PageOrderingRules;
+
+/// Updates which became invalid or valid after a change of the rules, by index.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ValidityChanges {
+    pub became_invalid: Vec<usize>,
+    pub became_valid: Vec<usize>,
+}
+
+impl ValidityChanges {
+    pub fn is_empty(&self) -> bool {
+        self.became_invalid.is_empty() && self.became_valid.is_empty()
+    }
+}
+
+/// Validity of the updates, kept up to date as rules are added and removed.
+///
+/// Only the updates holding both pages of a changed rule are checked again.
+#[derive(Debug, Clone)]
+pub struct ValidityTracker {
+    rules: PageOrderingRules,
+    updates: Vec<Vec<usize>>,
+    /// Indexes of each page in each update.
+    positions: Vec<HashMap<usize, Vec<usize>>>,
+    /// Updates holding each page.
+    updates_by_page: HashMap<usize, Vec<usize>>,
+    /// Number of times each update breaks the rules.
+    violations: Vec<usize>,
+}
+
+impl ValidityTracker {
+    pub fn new(rules: PageOrderingRules, updates: Vec<Vec<usize>>) -> Self {
+        let mut positions = Vec::with_capacity(updates.len());
+        let mut updates_by_page: HashMap<usize, Vec<usize>> = HashMap::new();
+
+        for (update, pagelist) in updates.iter().enumerate() {
+            let mut update_positions: HashMap<usize, Vec<usize>> = HashMap::new();
+            for (index, &page) in pagelist.iter().enumerate() {
+                update_positions.entry(page).or_default().push(index);
+            }
+            for &page in update_positions.keys() {
+                updates_by_page.entry(page).or_default().push(update);
+            }
+            positions.push(update_positions);
+        }
+
+        let violations = updates
+            .iter()
+            .map(|pagelist| rules.violations(pagelist).len())
+            .collect();
+
+        ValidityTracker {
+            rules,
+            updates,
+            positions,
+            updates_by_page,
+            violations,
+        }
+    }
+
+    pub fn rules(&self) -> &PageOrderingRules {
+        &self.rules
+    }
+
+    pub fn updates(&self) -> &[Vec<usize>] {
+        &self.updates
+    }
+
+    pub fn is_valid(&self, update: usize) -> bool {
+        self.violations[update] == 0
+    }
+
+    /// Indexes of the valid updates.
+    pub fn valid_updates(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.updates.len()).filter(|&update| self.is_valid(update))
+    }
+
+    /// Adds the rule `before|after`, nothing changing if it already exists.
+    pub fn add_rule(&mut self, rule: (usize, usize)) -> ValidityChanges {
+        if !self.rules.add_rule(rule) {
+            return ValidityChanges::default();
+        }
+
+        let mut changes = ValidityChanges::default();
+        for (update, broken) in self.broken_by(rule) {
+            if self.violations[update] == 0 {
+                changes.became_invalid.push(update);
+            }
+            self.violations[update] += broken;
+        }
+        changes
+    }
+
+    /// Removes the rule `before|after`, nothing changing if it doesn't exist.
+    pub fn remove_rule(&mut self, rule: (usize, usize)) -> ValidityChanges {
+        if !self.rules.remove_rule(rule) {
+            return ValidityChanges::default();
+        }
+
+        let mut changes = ValidityChanges::default();
+        for (update, broken) in self.broken_by(rule) {
+            self.violations[update] -= broken;
+            if self.violations[update] == 0 {
+                changes.became_valid.push(update);
+            }
+        }
+        changes
+    }
+
+    /// Updates breaking the rule, in increasing order, with the number of times they break it.
+    fn broken_by(&self, (before, after): (usize, usize)) -> Vec<(usize, usize)> {
+        let (Some(with_before), Some(with_after)) = (
+            self.updates_by_page.get(&before),
+            self.updates_by_page.get(&after),
+        ) else {
+            return Vec::new();
+        };
+
+        // go through the updates holding the rarest page of the two
+        let candidates = if with_before.len() <= with_after.len() {
+            with_before
+        } else {
+            with_after
+        };
+
+        candidates
+            .iter()
+            .filter_map(|&update| {
+                let positions = &self.positions[update];
+                let (befores, afters) = (positions.get(&before)?, positions.get(&after)?);
+                let broken = befores
+                    .iter()
+                    .map(|b| afters.iter().filter(|&a| a < b).count())
+                    .sum::<usize>();
+
+                (broken > 0).then_some((update, broken))
+            })
+            .collect()
+    }
+}
+
+impl From<InputData> for ValidityTracker {
+    fn from(data: InputData) -> Self {
+        ValidityTracker::new(data.ordering_rules, data.updates_pagelist)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    const SAMPLE: &str = include_str!("../../sample.txt");
+
+    #[test]
+    fn test_initial_validity() {
+        let tracker = ValidityTracker::from(InputData::from_str(SAMPLE).unwrap());
+
+        assert_eq!(tracker.valid_updates().collect::<Vec<_>>(), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn test_add_and_remove_rule() {
+        let mut tracker = ValidityTracker::from(InputData::from_str(SAMPLE).unwrap());
+
+        // 75,97,47,61,53 only breaks 97|75
+        assert_eq!(
+            tracker.remove_rule((97, 75)),
+            ValidityChanges {
+                became_invalid: vec![],
+                became_valid: vec![3],
+            }
+        );
+        assert!(tracker.remove_rule((97, 75)).is_empty());
+        assert_eq!(
+            tracker.add_rule((29, 53)),
+            ValidityChanges {
+                became_invalid: vec![0, 1],
+                became_valid: vec![],
+            }
+        );
+        assert!(tracker.add_rule((29, 53)).is_empty());
+        assert_eq!(
+            tracker.add_rule((97, 75)),
+            ValidityChanges {
+                became_invalid: vec![3],
+                became_valid: vec![],
+            }
+        );
+        assert_eq!(tracker.valid_updates().collect::<Vec<_>>(), vec![2]);
+    }
+
+    #[test]
+    fn test_rule_broken_by_an_already_invalid_update() {
+        let mut tracker = ValidityTracker::new(
+            PageOrderingRules::from_iter(vec![(1, 2)]),
+            vec![vec![2, 1, 4, 3]],
+        );
+
+        assert!(tracker.add_rule((3, 4)).is_empty());
+        assert!(tracker.remove_rule((1, 2)).is_empty());
+        assert_eq!(tracker.remove_rule((3, 4)).became_valid, vec![0]);
+    }
+
+    #[test]
+    fn test_matches_full_validation() {
+        let data = InputData::from_str(SAMPLE).unwrap();
+        let mut tracker = ValidityTracker::from(InputData::from_str(SAMPLE).unwrap());
+        let rules = data.ordering_rules().rules();
+
+        // remove every other rule, then add back rules in reverse
+        for (step, &rule) in rules.iter().enumerate().step_by(2) {
+            tracker.remove_rule(rule);
+            assert_valid_updates_match(&tracker, step);
+        }
+        for (step, &rule) in rules.iter().rev().enumerate() {
+            tracker.add_rule(rule);
+            assert_valid_updates_match(&tracker, step);
+        }
+        tracker.add_rule((13, 97));
+        assert_valid_updates_match(&tracker, rules.len());
+    }
+
+    fn assert_valid_updates_match(tracker: &ValidityTracker, step: usize) {
+        for (update, pagelist) in tracker.updates().iter().enumerate() {
+            assert_eq!(
+                tracker.is_valid(update),
+                tracker.rules().pagelist_is_valid(pagelist),
+                "update {update} at step {step}"
+            );
+        }
+    }
+}
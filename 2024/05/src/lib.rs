@@ -0,0 +1,107 @@
+mod input_data;
+mod middle;
+
+use std::error::Error;
+use std::str::FromStr;
+
+use aoc_common::Answer;
+use aoc_common::Solution;
+
+pub use self::input_data::CycleError;
+pub use self::input_data::FixedPagelist;
+pub use self::input_data::InputData;
+pub use self::input_data::InputDataError;
+pub use self::input_data::Orderings;
+pub use self::input_data::PageOrderingRules;
+pub use self::input_data::Section;
+pub use self::input_data::ValidityChanges;
+pub use self::input_data::ValidityTracker;
+pub use self::input_data::Violation;
+pub use self::middle::middle_pages;
+pub use self::middle::MiddleError;
+pub use self::middle::MiddlePolicy;
+
+pub fn sum_of_middles<T: AsRef<[usize]>>(
+    pagelists: &[T],
+    policy: MiddlePolicy,
+) -> Result<usize, MiddleError> {
+    pagelists.iter().try_fold(0, |sum, pagelist| {
+        Ok(sum
+            + middle_pages(pagelist.as_ref(), policy)?
+                .iter()
+                .sum::<usize>())
+    })
+}
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 5;
+
+    type Input = InputData;
+
+    fn parse(input: &str) -> Result<Self::Input, Box<dyn Error>> {
+        Ok(InputData::from_str(input)?)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Answer, Box<dyn Error>> {
+        Ok(sum_of_middles(&input.get_correct_updates(), MiddlePolicy::default())?.into())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Answer, Box<dyn Error>> {
+        Ok(sum_of_middles(&input.fix_incorrect_updates()?, MiddlePolicy::default())?.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = include_str!("../sample.txt");
+
+    #[test]
+    fn test_sum_of_middles() {
+        let data = InputData::from_str(SAMPLE).unwrap();
+        assert_eq!(
+            sum_of_middles(&data.get_correct_updates(), MiddlePolicy::Error),
+            Ok(143)
+        );
+    }
+
+    #[test]
+    fn test_fix_incorrect_updates() {
+        let data = InputData::from_str(SAMPLE).unwrap();
+        assert_eq!(
+            sum_of_middles(&data.fix_incorrect_updates().unwrap(), MiddlePolicy::Error),
+            Ok(123)
+        );
+    }
+
+    #[test]
+    fn test_sum_of_middles_policies() {
+        let pagelists = [vec![1, 2, 3], vec![4, 5, 6, 7]];
+
+        assert_eq!(
+            sum_of_middles(&pagelists, MiddlePolicy::Error),
+            Err(MiddleError::EvenLength {
+                pages: vec![4, 5, 6, 7]
+            })
+        );
+        assert_eq!(sum_of_middles(&pagelists, MiddlePolicy::Lower), Ok(7));
+        assert_eq!(sum_of_middles(&pagelists, MiddlePolicy::Upper), Ok(8));
+        assert_eq!(sum_of_middles(&pagelists, MiddlePolicy::Both), Ok(13));
+        assert_eq!(
+            sum_of_middles(&[vec![1], vec![]], MiddlePolicy::Upper),
+            Ok(1)
+        );
+    }
+
+    #[test]
+    fn test_solution() {
+        let input = Day05::parse(SAMPLE).unwrap();
+
+        assert_eq!(Day05::part1(&input).unwrap(), Answer::from(143));
+        assert_eq!(Day05::part2(&input).unwrap(), Answer::from(123));
+    }
+}
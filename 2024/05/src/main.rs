@@ -1,52 +1,306 @@
-mod input_data;
-
+use std::error::Error;
+use std::fs;
+use std::io;
+use std::io::BufRead;
 use std::io::Read;
-use std::str::FromStr;
+use std::path::PathBuf;
+
+use aoc2405::Day05;
+use aoc2405::InputData;
+use aoc2405::MiddlePolicy;
+use aoc2405::ValidityTracker;
+use aoc_common::Solution;
+use structopt::StructOpt;
+
+/// Solves the puzzle for the rules and updates read from stdin.
+#[derive(Debug, StructOpt)]
+#[structopt(name = "aoc2405")]
+struct Opt {
+    /// Prints whether each update is correct and the rules broken by the incorrect ones,
+    /// instead of solving the puzzle
+    #[structopt(long)]
+    explain: bool,
+
+    /// Prints the groups of pages the rules order in a cycle and the rules implied by others,
+    /// instead of solving the puzzle
+    #[structopt(long)]
+    analyze: bool,
+
+    /// Tells whether the rules require the first page to come before the second one, directly
+    /// or through other pages, given as "before,after"
+    #[structopt(long, parse(try_from_str = parse_pages))]
+    precedes: Option<(usize, usize)>,
+
+    /// Writes the rule graph to a Graphviz file instead of solving the puzzle
+    #[structopt(long, parse(from_os_str))]
+    dot: Option<PathBuf>,
+
+    /// Prints how many orders of each update follow the rules, listing up to the given number
+    /// of them, instead of solving the puzzle
+    #[structopt(long)]
+    orderings: Option<usize>,
+
+    /// Solves the puzzle taking the lower, upper or both middle pages of updates with an even
+    /// number of pages, or "error", listing the updates without a single middle page
+    #[structopt(long)]
+    middle: Option<MiddlePolicy>,
 
-use self::input_data::InputData;
+    /// Reads the rules and updates from the given file, then commands from stdin, one per line:
+    /// "+before|after" adds a rule, "-before|after" removes it, and "?" lists the valid updates.
+    /// Prints the updates whose validity changes after each command
+    #[structopt(long, parse(from_os_str))]
+    edit: Option<PathBuf>,
 
-fn sum_of_middles<T: AsRef<[usize]>>(pagelists: &[T]) -> usize {
-    pagelists.iter().fold(0, |sum, pagelist| {
-        let pagelist = pagelist.as_ref();
-        sum + pagelist[pagelist.len() / 2]
-    })
+    /// Restricts --analyze, --precedes, --dot and --orderings to the pages of the given update,
+    /// numbered from 1
+    #[structopt(long)]
+    update: Option<usize>,
 }
 
-fn main() -> Result<(), Box<dyn std::error::Error>> {
-    println!("Hello, Advent of Code 2024!");
-    println!("--- Day 5 ---");
+fn parse_pages(s: &str) -> Result<(usize, usize), Box<dyn Error>> {
+    let (before, after) = s.split_once(',').ok_or("expected \"before,after\"")?;
+    Ok((before.trim().parse()?, after.trim().parse()?))
+}
+
+fn parse_rule(s: &str) -> Result<(usize, usize), Box<dyn Error>> {
+    let (before, after) = s.split_once('|').ok_or("expected \"before|after\"")?;
+    Ok((before.trim().parse()?, after.trim().parse()?))
+}
 
+fn read_data() -> Result<InputData, Box<dyn Error>> {
     let mut input = String::new();
-    std::io::stdin().read_to_string(&mut input)?;
+    io::stdin().read_to_string(&mut input)?;
+    Day05::parse(&input)
+}
+
+fn update_pages<'d>(opt: &Opt, data: &'d InputData) -> Result<Option<&'d [usize]>, Box<dyn Error>> {
+    match opt.update {
+        Some(number) => match number
+            .checked_sub(1)
+            .and_then(|index| data.updates().get(index))
+        {
+            Some(pages) => Ok(Some(pages)),
+            None => Err(format!("there is no update {number}").into()),
+        },
+        None => Ok(None),
+    }
+}
+
+fn graph(opt: &Opt) -> Result<(), Box<dyn Error>> {
+    let data = read_data()?;
+    let pages = update_pages(opt, &data)?;
+    let restricted;
+    let rules = match pages {
+        Some(pages) => {
+            restricted = data.ordering_rules().restricted_to(pages);
+            &restricted
+        }
+        None => data.ordering_rules(),
+    };
+
+    if let Some((before, after)) = opt.precedes {
+        let answer = if rules.must_precede(before, after) {
+            "must"
+        } else {
+            "need not"
+        };
+        println!("{before} {answer} come before {after}");
+    }
+
+    if opt.analyze {
+        let cycles: Vec<_> = rules
+            .strongly_connected_components()
+            .into_iter()
+            .filter(|component| component.len() > 1)
+            .collect();
+        println!("Cycles: {}", cycles.len());
+        for cycle in cycles {
+            let pages: Vec<_> = cycle.iter().map(usize::to_string).collect();
+            println!("    {}", pages.join(","));
+        }
+
+        let redundant = rules.redundant_rules();
+        println!("Redundant rules: {}", redundant.len());
+        for (before, after) in redundant {
+            println!("    {before}|{after}");
+        }
+    }
+
+    if let Some(path) = &opt.dot {
+        fs::write(path, data.ordering_rules().to_dot(pages))?;
+        println!("Rule graph written to {}", path.display());
+    }
+
+    Ok(())
+}
+
+fn orderings(opt: &Opt, cap: usize) -> Result<(), Box<dyn Error>> {
+    let data = read_data()?;
+    let rules = data.ordering_rules();
+    let updates = match update_pages(opt, &data)? {
+        Some(pages) => vec![(opt.update.unwrap(), pages)],
+        None => (1..)
+            .zip(data.updates().iter().map(Vec::as_slice))
+            .collect(),
+    };
+
+    for (number, pagelist) in updates {
+        let unique = if rules.determines_order(pagelist) {
+            ", unique"
+        } else {
+            ""
+        };
+        match rules.count_orderings(pagelist) {
+            Some(count) => println!("Update {number}: valid orders: {count}{unique}"),
+            None => println!("Update {number}: valid orders: too many pages to count{unique}"),
+        }
+
+        for ordering in rules.orderings(pagelist).take(cap) {
+            let pages: Vec<_> = ordering.iter().map(usize::to_string).collect();
+            println!("    {}", pages.join(","));
+        }
+    }
 
-    let data = InputData::from_str(&input)?;
+    Ok(())
+}
+
+fn solve_with_policy(policy: MiddlePolicy) -> Result<(), Box<dyn Error>> {
+    let data = read_data()?;
+
+    for (number, pagelist) in (1..).zip(data.updates()) {
+        match aoc2405::middle_pages(pagelist, policy) {
+            Err(_) => println!(
+                "Update {number}: {} pages, no middle page ({policy})",
+                pagelist.len()
+            ),
+            Ok(middle) if pagelist.len() % 2 == 0 => println!(
+                "Update {number}: {} pages, middle {middle:?} ({policy})",
+                pagelist.len()
+            ),
+            Ok(_) => (),
+        }
+    }
+
+    match aoc2405::sum_of_middles(&data.get_correct_updates(), policy) {
+        Ok(sum) => println!("Part 1: {sum}"),
+        Err(error) => println!("Part 1 failed: {error}"),
+    }
+    match data.fix_incorrect_updates() {
+        Ok(fixed) => match aoc2405::sum_of_middles(&fixed, policy) {
+            Ok(sum) => println!("Part 2: {sum}"),
+            Err(error) => println!("Part 2 failed: {error}"),
+        },
+        Err(error) => println!("Part 2 failed: {error}"),
+    }
+
+    Ok(())
+}
+
+fn edit(path: &PathBuf) -> Result<(), Box<dyn Error>> {
+    let data = Day05::parse(&fs::read_to_string(path)?)?;
+    let mut tracker = ValidityTracker::from(data);
+    let update_text = |tracker: &ValidityTracker, update: usize| {
+        let pages: Vec<_> = tracker.updates()[update]
+            .iter()
+            .map(usize::to_string)
+            .collect();
+        format!("Update {}: {}", update + 1, pages.join(","))
+    };
+
+    for line in io::stdin().lock().lines() {
+        let line = line?;
+        let command = line.trim();
+
+        let changes = match command.chars().next() {
+            Some('+') | Some('-') => match parse_rule(&command[1..]) {
+                Ok(rule) if command.starts_with('+') => tracker.add_rule(rule),
+                Ok(rule) => tracker.remove_rule(rule),
+                Err(error) => {
+                    println!("Invalid rule {:?}: {error}", &command[1..]);
+                    continue;
+                }
+            },
+            Some('?') => {
+                for update in tracker.valid_updates() {
+                    println!("{}", update_text(&tracker, update));
+                }
+                continue;
+            }
+            None => continue,
+            Some(_) => {
+                println!("Unknown command {command:?}, expected +before|after, -before|after or ?");
+                continue;
+            }
+        };
+
+        if changes.is_empty() {
+            println!("No update changed");
+        }
+        for update in changes.became_invalid {
+            println!("{} became invalid", update_text(&tracker, update));
+        }
+        for update in changes.became_valid {
+            println!("{} became valid", update_text(&tracker, update));
+        }
+    }
 
-    let sum_of_middles_of_correct_updates = sum_of_middles(&data.get_correct_updates());
-    let sum_of_middles_of_incorrect_updates = sum_of_middles(&data.fix_incorrect_updates());
+    Ok(())
+}
+
+fn explain() -> Result<(), Box<dyn Error>> {
+    let data = read_data()?;
+    let mut incorrect = 0;
+
+    for (index, (pagelist, violations)) in data.violations_by_update().enumerate() {
+        let pages: Vec<_> = pagelist.iter().map(usize::to_string).collect();
+        let pages = pages.join(",");
 
-    println!("Part 1: sum of middles of correct updates is {sum_of_middles_of_correct_updates}");
-    println!(
-        "Part 2: sum of middles of incorrect, but fixed, updates is {sum_of_middles_of_incorrect_updates}"
-    );
+        if violations.is_empty() {
+            println!("Update {}: {pages}: correct", index + 1);
+            continue;
+        }
+
+        incorrect += 1;
+        println!(
+            "Update {}: {pages}: incorrect, rules broken: {}",
+            index + 1,
+            violations.len()
+        );
+        for violation in violations {
+            println!("    {violation}");
+        }
+    }
+
+    println!("Incorrect updates: {incorrect}");
 
     Ok(())
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+fn main() -> Result<(), Box<dyn Error>> {
+    let opt = Opt::from_args();
+
+    if opt.explain {
+        return explain();
+    }
+
+    if let Some(path) = &opt.edit {
+        return edit(path);
+    }
 
-    const SAMPLE: &str = include_str!("../sample.txt");
+    if let Some(policy) = opt.middle {
+        return solve_with_policy(policy);
+    }
 
-    #[test]
-    fn test_sum_of_middles() {
-        let data = InputData::from_str(SAMPLE).unwrap();
-        assert_eq!(sum_of_middles(&data.get_correct_updates()), 143);
+    if let Some(cap) = opt.orderings {
+        return orderings(&opt, cap);
     }
 
-    #[test]
-    fn test_fix_incorrect_updates() {
-        let data = InputData::from_str(SAMPLE).unwrap();
-        assert_eq!(sum_of_middles(&data.fix_incorrect_updates()), 123);
+    if opt.analyze || opt.precedes.is_some() || opt.dot.is_some() {
+        return graph(&opt);
     }
+
+    println!("Hello, Advent of Code 2024!");
+    println!("--- Day 5 ---");
+
+    aoc_common::solve_stdin::<aoc2405::Day05>()
 }
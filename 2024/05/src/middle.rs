@@ -0,0 +1,140 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// Which pages count as the middle of an update with an even number of pages.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum MiddlePolicy {
+    /// Even updates have no middle page, as the puzzle never gives any.
+    #[default]
+    Error,
+    /// The first of the two middle pages.
+    Lower,
+    /// The second of the two middle pages.
+    Upper,
+    /// Both middle pages, counted in the sum.
+    Both,
+}
+
+impl Display for MiddlePolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MiddlePolicy::Error => write!(f, "error"),
+            MiddlePolicy::Lower => write!(f, "lower"),
+            MiddlePolicy::Upper => write!(f, "upper"),
+            MiddlePolicy::Both => write!(f, "both"),
+        }
+    }
+}
+
+impl FromStr for MiddlePolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "error" => Ok(MiddlePolicy::Error),
+            "lower" => Ok(MiddlePolicy::Lower),
+            "upper" => Ok(MiddlePolicy::Upper),
+            "both" => Ok(MiddlePolicy::Both),
+            other => Err(format!(
+                "unknown middle policy \"{other}\", expected error, lower, upper or both"
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MiddleError {
+    /// Update with an even number of pages, with the `Error` policy.
+    EvenLength { pages: Vec<usize> },
+}
+
+impl Display for MiddleError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MiddleError::EvenLength { pages } => write!(
+                f,
+                "Update {pages:?} has no middle page, with {} pages",
+                pages.len()
+            ),
+        }
+    }
+}
+
+impl Error for MiddleError {}
+
+/// Middle page of the update, or its two middle pages with an even number of pages and the
+/// `Both` policy.
+///
+/// Updates read from the input are never empty, a blank line among them being rejected when
+/// parsing. An empty list still counts as even, with no page to pick under any policy but
+/// `Error`.
+pub fn middle_pages(pagelist: &[usize], policy: MiddlePolicy) -> Result<&[usize], MiddleError> {
+    let half = pagelist.len() / 2;
+
+    if pagelist.len() % 2 == 1 {
+        return Ok(&pagelist[half..=half]);
+    }
+
+    match policy {
+        MiddlePolicy::Error => Err(MiddleError::EvenLength {
+            pages: pagelist.to_vec(),
+        }),
+        _ if pagelist.is_empty() => Ok(&[]),
+        MiddlePolicy::Lower => Ok(&pagelist[half - 1..half]),
+        MiddlePolicy::Upper => Ok(&pagelist[half..=half]),
+        MiddlePolicy::Both => Ok(&pagelist[half - 1..=half]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_middle_pages_of_odd_update() {
+        for policy in [
+            MiddlePolicy::Error,
+            MiddlePolicy::Lower,
+            MiddlePolicy::Upper,
+            MiddlePolicy::Both,
+        ] {
+            assert_eq!(middle_pages(&[1, 2, 3], policy), Ok(&[2][..]));
+        }
+    }
+
+    #[test]
+    fn test_middle_pages_of_even_update() {
+        let pagelist = [1, 2, 3, 4];
+
+        assert_eq!(
+            middle_pages(&pagelist, MiddlePolicy::Error),
+            Err(MiddleError::EvenLength {
+                pages: vec![1, 2, 3, 4]
+            })
+        );
+        assert_eq!(middle_pages(&pagelist, MiddlePolicy::Lower), Ok(&[2][..]));
+        assert_eq!(middle_pages(&pagelist, MiddlePolicy::Upper), Ok(&[3][..]));
+        assert_eq!(middle_pages(&pagelist, MiddlePolicy::Both), Ok(&[2, 3][..]));
+    }
+
+    #[test]
+    fn test_middle_pages_of_empty_update() {
+        assert_eq!(
+            middle_pages(&[], MiddlePolicy::Error),
+            Err(MiddleError::EvenLength { pages: vec![] })
+        );
+        assert_eq!(middle_pages(&[], MiddlePolicy::Upper), Ok(&[][..]));
+        assert_eq!(middle_pages(&[], MiddlePolicy::Both), Ok(&[][..]));
+    }
+
+    #[test]
+    fn test_parse_middle_policy() {
+        assert_eq!("lower".parse(), Ok(MiddlePolicy::Lower));
+        assert_eq!(
+            MiddlePolicy::Both.to_string().parse(),
+            Ok(MiddlePolicy::Both)
+        );
+        assert!("middle".parse::<MiddlePolicy>().is_err());
+    }
+}
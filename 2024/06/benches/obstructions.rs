@@ -0,0 +1,73 @@
+//! Compares walking the guard step by step against jumping from obstruction to obstruction,
+//! looking for the new obstructions that trap the guard in a loop (2024, day 6, part 2) on a
+//! large generated map.
+
+use std::hint::black_box;
+use std::num::NonZeroUsize;
+use std::thread;
+
+use aoc2406::possible_new_obstructions;
+use aoc2406::possible_new_obstructions_parallel;
+use aoc2406::possible_new_obstructions_with_jumps;
+use aoc2406::Map;
+use criterion::criterion_group;
+use criterion::criterion_main;
+use criterion::Criterion;
+
+const SIZE: usize = 200;
+
+/// One cell in this many is an obstruction.
+const SPARSENESS: u64 = 20;
+
+/// A square map with obstructions spread by a xorshift generator and the guard in its middle,
+/// facing north.
+fn generated_map() -> Map {
+    let mut state = 0x2545_f491_4f6c_dd1d_u64;
+    let mut next_random = move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state
+    };
+
+    let map = (0..SIZE)
+        .map(|row| {
+            (0..SIZE)
+                .map(|col| match (row, col) {
+                    (row, col) if (row, col) == (SIZE / 2, SIZE / 2) => '^',
+                    _ if next_random() % SPARSENESS == 0 => '#',
+                    _ => '.',
+                })
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    map.parse().unwrap()
+}
+
+fn bench_possible_new_obstructions(c: &mut Criterion) {
+    let map = generated_map();
+
+    assert_eq!(
+        possible_new_obstructions(map.clone()),
+        possible_new_obstructions_with_jumps(&map).unwrap()
+    );
+
+    let mut group = c.benchmark_group("possible_new_obstructions");
+    group.sample_size(10);
+    group.bench_function("steps", |b| {
+        b.iter(|| possible_new_obstructions(black_box(map.clone())))
+    });
+    group.bench_function("jumps", |b| {
+        b.iter(|| possible_new_obstructions_with_jumps(black_box(&map)))
+    });
+    let workers = thread::available_parallelism().unwrap_or(NonZeroUsize::MIN);
+    group.bench_function("jumps_parallel", |b| {
+        b.iter(|| possible_new_obstructions_parallel(black_box(&map), workers))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_possible_new_obstructions);
+criterion_main!(benches);
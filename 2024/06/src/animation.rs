@@ -0,0 +1,189 @@
+use std::collections::HashSet;
+use std::io;
+use std::io::BufRead;
+use std::io::Write;
+use std::thread;
+use std::time::Duration;
+
+use aoc_grid::Coordinate;
+
+use crate::map::Map;
+use crate::Guard;
+use crate::GuardError;
+use crate::Loop;
+
+/// Clears the terminal and moves the cursor to its top left corner.
+const CLEAR_SCREEN: &str = "\x1b[2J\x1b[H";
+const HIGHLIGHT: &str = "\x1b[1;31m";
+const RESET: &str = "\x1b[0m";
+
+/// Terminal playback of the guard's patrol, drawing the map after each move.
+#[derive(Debug, Clone)]
+pub struct Animation {
+    /// Time to wait between two frames.
+    pub delay: Duration,
+    /// Waits for a line on the input between two frames instead of the delay.
+    pub step_by_step: bool,
+}
+
+impl Default for Animation {
+    fn default() -> Self {
+        Animation {
+            delay: Duration::from_millis(100),
+            step_by_step: false,
+        }
+    }
+}
+
+impl Animation {
+    /// Plays the patrol until the guard leaves the map or walks in a loop, which is then
+    /// highlighted in the last frame and returned.
+    ///
+    /// In step by step mode, reaching the end of `input` plays the rest of the patrol with the
+    /// delay.
+    pub fn play(
+        &self,
+        map: Map,
+        output: &mut impl Write,
+        input: &mut impl BufRead,
+    ) -> io::Result<Option<Loop>> {
+        let mut guard = Guard::new(map);
+        let mut step_by_step = self.step_by_step;
+        let mut steps = 0;
+
+        write!(output, "{CLEAR_SCREEN}{guard}")?;
+        writeln!(output, "Step {steps}")?;
+
+        while guard.current_position.is_some() {
+            guard.r#move();
+            steps += 1;
+
+            if let Err(GuardError::LoopDetected(cycle)) = guard.detect_loop() {
+                let points = cycle
+                    .positions
+                    .iter()
+                    .map(|position| position.point)
+                    .collect();
+                write!(output, "{CLEAR_SCREEN}")?;
+                write_highlighted(output, &guard.to_string(), &points)?;
+                writeln!(
+                    output,
+                    "Step {steps}: loop detected, {} positions long",
+                    cycle.len()
+                )?;
+                return Ok(Some(cycle));
+            }
+
+            if step_by_step {
+                write!(output, "Press Enter for the next step")?;
+                output.flush()?;
+                step_by_step = input.read_line(&mut String::new())? > 0;
+            } else {
+                output.flush()?;
+                thread::sleep(self.delay);
+            }
+
+            write!(output, "{CLEAR_SCREEN}{guard}")?;
+            writeln!(output, "Step {steps}")?;
+        }
+
+        writeln!(output, "The guard left the map after {steps} steps")?;
+        Ok(None)
+    }
+}
+
+/// Writes a frame, highlighting the given points.
+fn write_highlighted(
+    output: &mut impl Write,
+    frame: &str,
+    points: &HashSet<Coordinate>,
+) -> io::Result<()> {
+    for (row, line) in frame.lines().enumerate() {
+        for (col, elem) in line.chars().enumerate() {
+            if points.contains(&Coordinate::new(row, col)) {
+                write!(output, "{HIGHLIGHT}{elem}{RESET}")?;
+            } else {
+                write!(output, "{elem}")?;
+            }
+        }
+        writeln!(output)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_grid::Direction;
+
+    use super::*;
+    use crate::map::Position;
+
+    const SAMPLE: &str = include_str!("../sample.txt");
+
+    fn animation() -> Animation {
+        Animation {
+            delay: Duration::ZERO,
+            step_by_step: false,
+        }
+    }
+
+    #[test]
+    fn test_play_until_out_of_grid() {
+        let map: Map = SAMPLE.parse().unwrap();
+        let mut output = Vec::new();
+
+        let cycle = animation()
+            .play(map, &mut output, &mut io::empty())
+            .unwrap();
+
+        let output = String::from_utf8(output).unwrap();
+        assert_eq!(cycle, None);
+        assert_eq!(output.matches(CLEAR_SCREEN).count(), 56);
+        assert!(output.ends_with("The guard left the map after 55 steps\n"));
+        assert!(!output.contains(HIGHLIGHT));
+    }
+
+    #[test]
+    fn test_play_until_loop() {
+        let mut map: Map = SAMPLE.parse().unwrap();
+        map.add_obstruction(Coordinate::new(6, 3));
+        let mut output = Vec::new();
+
+        let cycle = animation()
+            .play(map, &mut output, &mut io::empty())
+            .unwrap()
+            .unwrap();
+
+        let output = String::from_utf8(output).unwrap();
+        assert!(cycle.positions.contains(&Position {
+            point: Coordinate::new(6, 4),
+            direction: Direction::West,
+        }));
+        assert_eq!(
+            cycle.positions.iter().collect::<HashSet<_>>().len(),
+            cycle.len()
+        );
+        assert!(output.contains(HIGHLIGHT));
+        assert!(output.contains(&format!("loop detected, {} positions long", cycle.len())));
+    }
+
+    #[test]
+    fn test_play_step_by_step() {
+        let map: Map = SAMPLE.parse().unwrap();
+        let mut output = Vec::new();
+        let mut input = "\n\n".as_bytes();
+
+        Animation {
+            step_by_step: true,
+            ..animation()
+        }
+        .play(map, &mut output, &mut input)
+        .unwrap();
+
+        let output = String::from_utf8(output).unwrap();
+        // the third prompt reaches the end of the input, and the rest is played with the delay
+        assert_eq!(output.matches("Press Enter").count(), 3);
+        assert!(output.ends_with("The guard left the map after 55 steps\n"));
+    }
+}
@@ -0,0 +1,190 @@
+use std::collections::HashSet;
+
+use aoc_grid::Coordinate;
+use aoc_grid::Direction;
+use aoc_grid::Distance;
+use aoc_grid::Matrix;
+
+use crate::map::Map;
+use crate::map::Position;
+
+/// Steps from every cell of a map to the next obstruction in each direction, so that the guard
+/// can move from one obstruction to the next in a single jump instead of walking cell by cell.
+#[derive(Debug, Clone)]
+pub struct JumpTable {
+    /// One matrix per direction, indexed by `direction as usize`, holding for each cell the
+    /// number of steps to the next obstruction, or `None` if the guard leaves the grid first.
+    steps: Vec<Matrix<Option<usize>>>,
+}
+
+impl JumpTable {
+    pub fn new(map: &Map) -> Self {
+        JumpTable {
+            steps: Direction::ALL
+                .into_iter()
+                .map(|direction| steps_towards(map, direction))
+                .collect(),
+        }
+    }
+
+    /// Number of steps until the guard reaches an obstruction, either one of the map or the
+    /// `extra` one, or `None` if it leaves the grid first.
+    fn steps_to_obstruction(&self, position: Position, extra: Option<Coordinate>) -> Option<usize> {
+        let on_map = self.steps[position.direction as usize][position.point];
+        let extra = extra.and_then(|extra| steps_between(position, extra));
+
+        on_map.into_iter().chain(extra).min()
+    }
+
+    /// Position of the guard after walking up to the next obstruction and turning, or `None`
+    /// if it leaves the grid.
+    pub fn jump(&self, position: Position, extra: Option<Coordinate>) -> Option<Position> {
+        let steps = self.steps_to_obstruction(position, extra)? as isize - 1;
+        let distance = position.direction.distance();
+
+        Some(Position {
+            point: position.point
+                + Distance::new(distance.row_delta * steps, distance.col_delta * steps),
+            direction: position.direction.rotate_clockwise(),
+        })
+    }
+
+    /// Checks whether the guard walks in a loop from the given position, with an optional
+    /// obstruction added to the map.
+    pub fn is_loop(&self, start: Position, extra: Option<Coordinate>) -> bool {
+        let mut turns = HashSet::new();
+        let mut position = start;
+
+        while let Some(next) = self.jump(position, extra) {
+            if !turns.insert(next) {
+                return true;
+            }
+            position = next;
+        }
+
+        false
+    }
+}
+
+/// Steps from each cell of the map to the next obstruction towards the direction, filled from
+/// the far side of the grid so that the next cell is always known before the current one.
+fn steps_towards(map: &Map, direction: Direction) -> Matrix<Option<usize>> {
+    let (height, width) = (map.height(), map.width());
+    let distance = direction.distance();
+    let mut steps = Matrix::from_fn(height, width, |_| None);
+
+    for i in 0..height {
+        let row = if distance.row_delta > 0 {
+            height - 1 - i
+        } else {
+            i
+        };
+        for j in 0..width {
+            let col = if distance.col_delta > 0 {
+                width - 1 - j
+            } else {
+                j
+            };
+            let point = Coordinate::new(row, col);
+
+            steps[point] = steps.step(point, direction).and_then(|next| {
+                if map.is_point_obstructed(next) {
+                    Some(1)
+                } else {
+                    steps[next].map(|steps| steps + 1)
+                }
+            });
+        }
+    }
+
+    steps
+}
+
+/// Number of steps to reach `target` walking straight from the position, if it is ahead.
+fn steps_between(position: Position, target: Coordinate) -> Option<usize> {
+    let distance = position.direction.distance();
+    let row_delta = target.row as isize - position.point.row as isize;
+    let col_delta = target.col as isize - position.point.col as isize;
+
+    let steps = if distance.row_delta != 0 {
+        row_delta / distance.row_delta
+    } else {
+        col_delta / distance.col_delta
+    };
+
+    (steps > 0
+        && row_delta == steps * distance.row_delta
+        && col_delta == steps * distance.col_delta)
+        .then_some(steps as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = include_str!("../sample.txt");
+
+    #[test]
+    fn test_jump() {
+        let map: Map = SAMPLE.parse().unwrap();
+        let table = JumpTable::new(&map);
+
+        let position = table.jump(map.guard_start_position(), None).unwrap();
+        assert_eq!(position.point, Coordinate::new(1, 4));
+        assert_eq!(position.direction, Direction::East);
+
+        let position = table.jump(position, None).unwrap();
+        assert_eq!(position.point, Coordinate::new(1, 8));
+        assert_eq!(position.direction, Direction::South);
+    }
+
+    #[test]
+    fn test_jump_out_of_grid() {
+        let map: Map = "...\n.>.\n...".parse().unwrap();
+        let table = JumpTable::new(&map);
+
+        assert_eq!(table.jump(map.guard_start_position(), None), None);
+    }
+
+    #[test]
+    fn test_jump_with_extra_obstruction() {
+        let map: Map = SAMPLE.parse().unwrap();
+        let table = JumpTable::new(&map);
+
+        let position = table
+            .jump(map.guard_start_position(), Some(Coordinate::new(3, 4)))
+            .unwrap();
+        assert_eq!(position.point, Coordinate::new(4, 4));
+
+        // behind the guard
+        let position = table
+            .jump(map.guard_start_position(), Some(Coordinate::new(8, 4)))
+            .unwrap();
+        assert_eq!(position.point, Coordinate::new(1, 4));
+    }
+
+    #[test]
+    fn test_jump_diagonal() {
+        let map: Map = "....#\n.....\n..^..".parse().unwrap();
+        let table = JumpTable::new(&map);
+        let start = Position {
+            point: Coordinate::new(2, 2),
+            direction: Direction::NorthEast,
+        };
+
+        let position = table.jump(start, None).unwrap();
+        assert_eq!(position.point, Coordinate::new(1, 3));
+        assert_eq!(position.direction, Direction::SouthEast);
+    }
+
+    #[test]
+    fn test_is_loop() {
+        let map: Map = SAMPLE.parse().unwrap();
+        let table = JumpTable::new(&map);
+        let start = map.guard_start_position();
+
+        assert!(!table.is_loop(start, None));
+        assert!(table.is_loop(start, Some(Coordinate::new(6, 3))));
+        assert!(!table.is_loop(start, Some(Coordinate::new(0, 0))));
+    }
+}
@@ -0,0 +1,756 @@
+mod animation;
+mod jump;
+mod map;
+mod patrol;
+mod rules;
+mod steps;
+mod trace;
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::error::Error;
+use std::fmt::Display;
+use std::num::NonZeroUsize;
+use std::thread;
+
+use aoc_common::Answer;
+use aoc_common::Solution;
+use aoc_grid::Colour;
+use aoc_grid::Coordinate;
+use aoc_grid::Direction;
+use aoc_grid::Image;
+use aoc_grid::Matrix;
+
+pub use self::animation::Animation;
+use self::jump::JumpTable;
+pub use self::map::Map;
+pub use self::map::MapError;
+pub use self::map::Position;
+pub use self::patrol::simulate;
+pub use self::patrol::simulate_together;
+pub use self::rules::Rules;
+pub use self::rules::Turn;
+pub use self::steps::StepEvent;
+pub use self::steps::Steps;
+pub use self::trace::Trace;
+pub use self::trace::TraceError;
+
+/// A guard patrolling a map, remembering where it has been.
+#[derive(Debug, Clone)]
+pub struct Guard {
+    start: Position,
+    current_position: Option<Position>,
+    previous_positions: HashSet<Position>,
+    /// Positions the guard has left, in order, with the index of the next turn it would take.
+    path: Vec<(Position, usize)>,
+    previous_states: HashSet<(Position, usize)>,
+    previous_points: HashSet<Coordinate>,
+    map: Map,
+    rules: Rules,
+    turns_taken: usize,
+    /// Points the guard cannot walk into besides the obstructions, like other guards.
+    blocked: HashSet<Coordinate>,
+}
+
+impl Display for Guard {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for point in (0..self.map.height())
+            .flat_map(|line| (0..self.map.width()).map(move |column| Coordinate::new(line, column)))
+        {
+            // Guard position
+            if let Some(position) = self
+                .current_position
+                .iter()
+                .find(|&&position| position.point == point)
+            {
+                write!(f, "{}", position.direction)?;
+            }
+            // Obstructions
+            else if self.map.is_point_obstructed(point) {
+                write!(f, "#")?;
+            }
+            // Starting position
+            else if self.start == point {
+                write!(f, "G")?;
+            }
+            // Past positions
+            else if let Some(position) = self
+                .previous_positions
+                .iter()
+                .find(|&&position| position == point)
+            {
+                write!(
+                    f,
+                    "{}",
+                    match position.direction {
+                        Direction::North | Direction::South => '|',
+                        Direction::East | Direction::West => '-',
+                        Direction::NorthEast | Direction::SouthWest => '/',
+                        Direction::SouthEast | Direction::NorthWest => '\\',
+                    }
+                )?;
+            }
+            // Empty positions
+            else {
+                write!(f, ".")?;
+            }
+
+            // new line
+            if point.col == self.map.width() - 1 {
+                writeln!(f)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// A cycle of positions the guard walks forever once it enters it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Loop {
+    /// Number of moves before the guard first reaches the loop.
+    pub entry_step: usize,
+    /// Positions of the loop, in order, starting with the one the guard enters it by.
+    pub positions: Vec<Position>,
+}
+
+impl Loop {
+    /// Number of moves to walk the loop once.
+    pub fn len(&self) -> usize {
+        self.positions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.positions.is_empty()
+    }
+}
+
+impl Display for Loop {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "entered at step {}, {} positions long:",
+            self.entry_step,
+            self.len()
+        )?;
+        for position in &self.positions {
+            write!(f, " {position}")?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GuardError {
+    LoopDetected(Loop),
+    /// Several guards walking together came back to a state they were all in before.
+    PatrolLoopDetected {
+        entry_step: usize,
+        length: usize,
+    },
+    /// The guard was still on the map after the maximum number of steps.
+    StepLimitReached(usize),
+}
+
+impl Display for GuardError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GuardError::LoopDetected(cycle) => write!(f, "loop {cycle}"),
+            GuardError::PatrolLoopDetected { entry_step, length } => {
+                write!(f, "loop entered at step {entry_step}, {length} steps long")
+            }
+            GuardError::StepLimitReached(steps) => {
+                write!(f, "still on the map after the limit of {steps} steps")
+            }
+        }
+    }
+}
+
+impl Error for GuardError {}
+
+impl Guard {
+    /// The first guard of the map, following the rules of the puzzle.
+    pub fn new(map: Map) -> Guard {
+        Guard::with_rules(map.guard_start_position(), map, Rules::default())
+    }
+
+    pub fn with_rules(start: Position, map: Map, rules: Rules) -> Guard {
+        Guard {
+            start,
+            current_position: Some(start),
+            previous_positions: HashSet::new(),
+            path: Vec::new(),
+            previous_states: HashSet::new(),
+            previous_points: HashSet::new(),
+            map,
+            rules,
+            turns_taken: 0,
+            blocked: HashSet::new(),
+        }
+    }
+
+    /// Current position, or `None` once the guard has left the map.
+    pub fn position(&self) -> Option<Position> {
+        self.current_position
+    }
+
+    /// Moves the guard, step after step, until it leaves the map, walks in a loop or reaches
+    /// the step limit of its rules.
+    pub fn steps(&mut self) -> Steps<'_> {
+        Steps::new(self)
+    }
+
+    /// Current position with the index of the next turn in the rules, which together decide
+    /// where the guard goes from there.
+    fn state(&self) -> Option<(Position, usize)> {
+        self.current_position
+            .map(|position| (position, self.turns_taken % self.rules.turns().len()))
+    }
+
+    fn r#move(&mut self) -> Option<Position> {
+        if let Some(state @ (position, _)) = self.state() {
+            self.previous_positions.insert(position);
+            self.previous_states.insert(state);
+            self.path.push(state);
+            self.previous_points.insert(position.point);
+            self.current_position = match self.map.next_position(position) {
+                None => None,
+                Some(next_position)
+                    if self.map.is_point_obstructed(next_position.point)
+                        || self.blocked.contains(&next_position.point) =>
+                {
+                    let turn = self.rules.turn(self.turns_taken);
+                    self.turns_taken += 1;
+                    Some(Position {
+                        point: position.point,
+                        direction: turn.apply(position.direction),
+                    })
+                }
+                Some(next_position) => Some(next_position),
+            }
+        }
+
+        self.current_position
+    }
+
+    /// The loop the guard is in, if its current state is one it has already left.
+    fn detect_loop(&self) -> Result<(), GuardError> {
+        let Some(state) = self
+            .state()
+            .filter(|state| self.previous_states.contains(state))
+        else {
+            return Ok(());
+        };
+
+        let entry_step = self
+            .path
+            .iter()
+            .position(|&previous| previous == state)
+            .expect("previous states are all on the path");
+
+        Err(GuardError::LoopDetected(Loop {
+            entry_step,
+            positions: self.path[entry_step..]
+                .iter()
+                .map(|&(position, _)| position)
+                .collect(),
+        }))
+    }
+
+    /// Number of moves made so far.
+    fn steps_taken(&self) -> usize {
+        self.path.len()
+    }
+
+    /// Whether the guard is still on the map after the step limit of its rules.
+    fn step_limit_reached(&self) -> bool {
+        self.current_position.is_some()
+            && self
+                .rules
+                .max_steps
+                .is_some_and(|max_steps| self.steps_taken() >= max_steps)
+    }
+
+    fn move_until_out_of_grid(&mut self) -> Result<(), GuardError> {
+        for event in self.steps() {
+            match event {
+                StepEvent::LoopDetected(cycle) => return Err(GuardError::LoopDetected(cycle)),
+                StepEvent::StepLimitReached(steps) => {
+                    return Err(GuardError::StepLimitReached(steps))
+                }
+                StepEvent::Moved(_) | StepEvent::Turned(_) | StepEvent::LeftGrid => {}
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn unique_points(map: Map) -> HashSet<Coordinate> {
+    let mut guard = Guard::new(map);
+    let _ = guard.move_until_out_of_grid();
+
+    guard
+        .previous_positions
+        .iter()
+        .map(|p| p.point)
+        .collect::<HashSet<_>>()
+}
+
+/// Reference implementation of part 2, walking a clone of the guard step by step for every
+/// candidate obstruction.
+pub fn possible_new_obstructions(map: Map) -> HashSet<Coordinate> {
+    let mut guard = Guard::new(map);
+    let mut possible_new_obstructions = HashSet::new();
+
+    while let Some(position) = guard.current_position {
+        let Some(next_point) = guard.map.next_position(position).map(|p| p.point) else {
+            break;
+        };
+
+        if !guard.map.is_point_obstructed(next_point)
+            && !guard.previous_points.contains(&next_point)
+        {
+            let mut second_guard = guard.clone();
+            second_guard.map.add_obstruction(next_point);
+            if let Err(GuardError::LoopDetected(_)) = second_guard.move_until_out_of_grid() {
+                possible_new_obstructions.insert(next_point);
+            }
+        }
+
+        guard.r#move();
+    }
+
+    possible_new_obstructions
+}
+
+/// Points where an obstruction could be added on the guard's path, each with the position the
+/// guard would be in when first bumping into it.
+///
+/// Fails if the guard walks in a loop without any added obstruction.
+fn candidate_obstructions(map: &Map) -> Result<Vec<(Position, Coordinate)>, GuardError> {
+    let mut visited_points = HashSet::new();
+    // positions the guard has left, in order, with their index in the path
+    let mut path = Vec::new();
+    let mut visited_positions = HashMap::new();
+    let mut candidates = Vec::new();
+    let mut current_position = Some(map.guard_start_position());
+
+    while let Some(position) = current_position {
+        if let Some(&entry_step) = visited_positions.get(&position) {
+            return Err(GuardError::LoopDetected(Loop {
+                entry_step,
+                positions: path[entry_step..].to_vec(),
+            }));
+        }
+        visited_positions.insert(position, path.len());
+        path.push(position);
+        visited_points.insert(position.point);
+
+        current_position = match map.next_position(position) {
+            None => None,
+            Some(next_position) if map.is_point_obstructed(next_position.point) => {
+                Some(position.rotate_clockwise())
+            }
+            Some(next_position) => {
+                if !visited_points.contains(&next_position.point) {
+                    candidates.push((position, next_position.point));
+                }
+                Some(next_position)
+            }
+        };
+    }
+
+    Ok(candidates)
+}
+
+/// Same as [`possible_new_obstructions`], but checking each candidate with a [`JumpTable`].
+///
+/// Fails if the guard already walks in a loop without any added obstruction.
+pub fn possible_new_obstructions_with_jumps(map: &Map) -> Result<HashSet<Coordinate>, GuardError> {
+    let table = JumpTable::new(map);
+
+    Ok(candidate_obstructions(map)?
+        .into_iter()
+        .filter(|&(position, candidate)| table.is_loop(position, Some(candidate)))
+        .map(|(_, candidate)| candidate)
+        .collect())
+}
+
+/// Same as [`possible_new_obstructions_with_jumps`], but splitting the candidates between the
+/// given number of threads.
+pub fn possible_new_obstructions_parallel(
+    map: &Map,
+    workers: NonZeroUsize,
+) -> Result<HashSet<Coordinate>, GuardError> {
+    let table = JumpTable::new(map);
+    let candidates = candidate_obstructions(map)?;
+    let chunk_size = candidates.len().div_ceil(workers.get()).max(1);
+
+    thread::scope(|scope| {
+        let handles = candidates
+            .chunks(chunk_size)
+            .map(|chunk| {
+                let table = &table;
+                scope.spawn(move || {
+                    chunk
+                        .iter()
+                        .filter(|&&(position, candidate)| table.is_loop(position, Some(candidate)))
+                        .map(|&(_, candidate)| candidate)
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect::<Vec<_>>();
+
+        Ok(handles
+            .into_iter()
+            .flat_map(|handle| handle.join().expect("worker thread panicked"))
+            .collect())
+    })
+}
+
+/// Loop the guard walks in for each obstruction that can be added to trap it, ordered by the
+/// obstruction's coordinate.
+pub fn new_obstruction_loops(map: &Map) -> Result<Vec<(Coordinate, Loop)>, GuardError> {
+    let mut obstructions = possible_new_obstructions_with_jumps(map)?
+        .into_iter()
+        .collect::<Vec<_>>();
+    obstructions.sort_unstable();
+
+    Ok(obstructions
+        .into_iter()
+        .filter_map(|obstruction| {
+            let mut map = map.clone();
+            map.add_obstruction(obstruction);
+
+            match Guard::new(map).move_until_out_of_grid() {
+                Err(GuardError::LoopDetected(cycle)) => Some((obstruction, cycle)),
+                _ => None,
+            }
+        })
+        .collect())
+}
+
+/// Draws the map, with the points visited by the guard and the obstructions that would trap it
+/// in a loop.
+pub fn render(map: &Map) -> Image {
+    let visited = unique_points(map.clone());
+    // a guard already walking in a loop leaves no obstruction to suggest
+    let candidates = possible_new_obstructions_with_jumps(map).unwrap_or_default();
+
+    Image::new(Matrix::from_fn(map.height(), map.width(), |point| {
+        if map.guard_start_position() == point {
+            Colour::GREEN
+        } else if map.is_point_obstructed(point) {
+            Colour::BLACK
+        } else if candidates.contains(&point) {
+            Colour::RED
+        } else if visited.contains(&point) {
+            Colour::BLUE
+        } else {
+            Colour::WHITE
+        }
+    }))
+}
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 6;
+
+    type Input = Map;
+
+    fn parse(input: &str) -> Result<Self::Input, Box<dyn Error>> {
+        Ok(input.parse()?)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Answer, Box<dyn Error>> {
+        Ok(unique_points(input.clone()).len().into())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Answer, Box<dyn Error>> {
+        let workers = thread::available_parallelism().unwrap_or(NonZeroUsize::MIN);
+
+        Ok(possible_new_obstructions_parallel(input, workers)?
+            .len()
+            .into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    const SAMPLE: &str = include_str!("../sample.txt");
+
+    #[test]
+    fn test_guard_initial_position() {
+        let map: Map = SAMPLE.parse().unwrap();
+        let initial_position = Position {
+            point: Coordinate::new(6, 4),
+            direction: Direction::North,
+        };
+
+        let guard = Guard::new(map);
+
+        assert_eq!(guard.current_position, Some(initial_position));
+        assert_eq!(guard.previous_points.len(), 0);
+        assert_eq!(guard.previous_positions.len(), 0);
+    }
+
+    #[test]
+    fn test_guard_move() {
+        let map: Map = SAMPLE.parse().unwrap();
+        let mut guard = Guard::new(map);
+        let expected_position = Position {
+            point: Coordinate::new(5, 4),
+            direction: Direction::North,
+        };
+        let previous_position = guard.current_position.unwrap();
+
+        let new_position = guard.r#move().unwrap();
+
+        assert_eq!(new_position, expected_position);
+        assert_eq!(guard.previous_points.len(), 1);
+        assert!(guard.previous_points.contains(&previous_position.point));
+        assert_eq!(guard.previous_positions.len(), 1);
+        assert!(guard.previous_positions.contains(&previous_position));
+    }
+
+    #[test]
+    fn test_display_guard_on_non_square_map() {
+        let map: Map = "..#.\n\
+                        .<..\n"
+            .parse()
+            .unwrap();
+        let mut guard = Guard::new(map);
+
+        assert_eq!(guard.to_string(), "..#.\n.<..\n");
+
+        guard.r#move();
+        assert_eq!(guard.to_string(), "..#.\n<G..\n");
+    }
+
+    #[test]
+    fn test_count_unique_points() {
+        let map: Map = SAMPLE.parse().unwrap();
+
+        let unique_points = unique_points(map);
+
+        assert_eq!(unique_points.len(), 41);
+    }
+
+    #[test]
+    fn test_possible_new_obstructions() {
+        let map: Map = SAMPLE.parse().unwrap();
+
+        let obstructions = dbg!(possible_new_obstructions(map));
+
+        assert_eq!(obstructions.len(), 6);
+        assert_eq!(
+            obstructions,
+            HashSet::from([
+                Coordinate::new(6, 3),
+                Coordinate::new(7, 6),
+                Coordinate::new(7, 7),
+                Coordinate::new(8, 1),
+                Coordinate::new(8, 3),
+                Coordinate::new(9, 7),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_possible_new_obstructions_with_jumps() {
+        let maps = [
+            SAMPLE,
+            "....#.\n.#...#\n......\n#.^...\n....#.",
+            "#....\n.....\n..>.#\n.....\n...#.",
+            "...\n.>.\n...",
+        ];
+
+        for map in maps {
+            let map: Map = map.parse().unwrap();
+            assert_eq!(
+                possible_new_obstructions_with_jumps(&map).unwrap(),
+                possible_new_obstructions(map.clone()),
+                "failed for:\n{:?}",
+                map
+            );
+        }
+    }
+
+    /// Maps of the given size with obstructions spread by a xorshift generator, keeping only
+    /// those the guard leaves.
+    fn random_maps(size: usize, count: usize) -> Vec<Map> {
+        let mut state = 0x9e37_79b9_7f4a_7c15_u64;
+        let mut next_random = move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+
+        std::iter::repeat_with(|| {
+            let guard = (next_random() as usize % size, next_random() as usize % size);
+            (0..size)
+                .map(|row| {
+                    (0..size)
+                        .map(|col| match (row, col) {
+                            point if point == guard => '^',
+                            _ if next_random() % 8 == 0 => '#',
+                            _ => '.',
+                        })
+                        .collect::<String>()
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+                .parse::<Map>()
+                .unwrap()
+        })
+        .filter(|map| !JumpTable::new(map).is_loop(map.guard_start_position(), None))
+        .take(count)
+        .collect()
+    }
+
+    #[test]
+    fn test_possible_new_obstructions_parallel() {
+        let maps = std::iter::once(SAMPLE.parse().unwrap()).chain(random_maps(20, 10));
+
+        for map in maps {
+            let sequential = possible_new_obstructions_with_jumps(&map).unwrap();
+            for workers in [1, 3, 8] {
+                let workers = NonZeroUsize::new(workers).unwrap();
+                assert_eq!(
+                    possible_new_obstructions_parallel(&map, workers).unwrap(),
+                    sequential,
+                    "failed with {} workers for:\n{:?}",
+                    workers,
+                    map
+                );
+            }
+            assert_eq!(sequential, possible_new_obstructions(map));
+        }
+    }
+
+    #[test]
+    fn test_loop_detected() {
+        let map: Map = ".#..\n\
+                        ...#\n\
+                        #...\n\
+                        .^#."
+            .parse()
+            .unwrap();
+        let mut guard = Guard::new(map);
+
+        let Err(GuardError::LoopDetected(cycle)) = guard.move_until_out_of_grid() else {
+            panic!("the guard should walk in a loop");
+        };
+
+        let position = |row, col, direction| Position {
+            point: Coordinate::new(row, col),
+            direction,
+        };
+        assert_eq!(cycle.entry_step, 1);
+        assert_eq!(cycle.len(), 8);
+        assert_eq!(
+            cycle.positions,
+            vec![
+                position(2, 1, Direction::North),
+                position(1, 1, Direction::North),
+                position(1, 1, Direction::East),
+                position(1, 2, Direction::East),
+                position(1, 2, Direction::South),
+                position(2, 2, Direction::South),
+                position(2, 2, Direction::West),
+                position(2, 1, Direction::West),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_possible_new_obstructions_when_already_looping() {
+        let map: Map = ".#..\n\
+                        ...#\n\
+                        #...\n\
+                        .^#."
+            .parse()
+            .unwrap();
+        let Err(GuardError::LoopDetected(expected)) =
+            Guard::new(map.clone()).move_until_out_of_grid()
+        else {
+            panic!("the guard should walk in a loop");
+        };
+
+        for result in [
+            possible_new_obstructions_with_jumps(&map),
+            possible_new_obstructions_parallel(&map, NonZeroUsize::new(3).unwrap()),
+        ] {
+            match result {
+                Err(GuardError::LoopDetected(cycle)) => assert_eq!(cycle, expected),
+                result => panic!("expected a loop, got {result:?}"),
+            }
+        }
+        assert!(new_obstruction_loops(&map).is_err());
+    }
+
+    #[test]
+    fn test_new_obstruction_loops() {
+        let map: Map = SAMPLE.parse().unwrap();
+
+        let loops = new_obstruction_loops(&map).unwrap();
+
+        assert_eq!(
+            loops
+                .iter()
+                .map(|&(obstruction, _)| obstruction)
+                .collect::<Vec<_>>(),
+            vec![
+                Coordinate::new(6, 3),
+                Coordinate::new(7, 6),
+                Coordinate::new(7, 7),
+                Coordinate::new(8, 1),
+                Coordinate::new(8, 3),
+                Coordinate::new(9, 7),
+            ]
+        );
+        for (obstruction, cycle) in loops {
+            let mut map = map.clone();
+            map.add_obstruction(obstruction);
+            let mut guard = Guard::new(map);
+
+            for _ in 0..cycle.entry_step {
+                guard.r#move();
+            }
+            // walking the loop once from its entry comes back to it
+            for &position in &cycle.positions {
+                assert_eq!(guard.current_position, Some(position));
+                guard.r#move();
+            }
+            assert_eq!(guard.current_position, cycle.positions.first().copied());
+        }
+    }
+
+    #[test]
+    fn test_render() {
+        let map: Map = SAMPLE.parse().unwrap();
+
+        let image = render(&map);
+        let colour_at = |row, col| image.cells()[(row, col)];
+
+        assert_eq!(colour_at(6, 4), Colour::GREEN);
+        assert_eq!(colour_at(0, 4), Colour::BLACK);
+        assert_eq!(colour_at(6, 3), Colour::RED);
+        assert_eq!(colour_at(1, 4), Colour::BLUE);
+        assert_eq!(colour_at(0, 0), Colour::WHITE);
+    }
+
+    #[test]
+    fn test_solution() {
+        let input = Day06::parse(SAMPLE).unwrap();
+
+        assert_eq!(Day06::part1(&input).unwrap(), Answer::from(41));
+        assert_eq!(Day06::part2(&input).unwrap(), Answer::from(6));
+    }
+}
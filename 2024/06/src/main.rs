@@ -1,251 +1,248 @@
-mod map;
-
-use std::collections::HashSet;
-use std::fmt::Display;
+use std::error::Error;
+use std::fs;
+use std::io;
+use std::io::BufReader;
+use std::io::BufWriter;
 use std::io::Read;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use aoc2406::Animation;
+use aoc2406::Guard;
+use aoc2406::Map;
+use aoc2406::Rules;
+use aoc2406::Trace;
+use aoc_grid::Coordinate;
+use structopt::StructOpt;
+
+/// Solves the puzzle for the map read from stdin, or plays the guard's patrol in the terminal.
+#[derive(Debug, StructOpt)]
+#[structopt(name = "aoc2406")]
+struct Opt {
+    /// Plays the guard's patrol instead of solving the puzzle
+    #[structopt(long)]
+    animate: bool,
+
+    /// Delay between two frames of the animation, in milliseconds
+    #[structopt(long, default_value = "100")]
+    delay: u64,
+
+    /// Waits for Enter between two frames of the animation
+    #[structopt(long)]
+    step: bool,
+
+    /// Obstruction to add to the map before the animation, as "row,column"
+    #[structopt(long, parse(try_from_str = parse_coordinate))]
+    obstruction: Option<Coordinate>,
+
+    /// Prints the loop the guard walks in for each obstruction that can be added to trap it,
+    /// instead of solving the puzzle
+    #[structopt(long)]
+    loops: bool,
+
+    /// Draws the map, the guard's path and the possible new obstructions to an image file
+    /// (.ppm, .png or .svg) instead of solving the puzzle
+    #[structopt(long, parse(from_os_str))]
+    image: Option<PathBuf>,
+
+    /// Walks every guard of the map with the given rules, instead of solving the puzzle
+    #[structopt(long)]
+    simulate: bool,
+
+    /// Turns the guards take on obstructions: "clockwise", "counter-clockwise", "reverse" or a
+    /// comma separated sequence of "right", "left" and "back"
+    #[structopt(long, default_value = "clockwise")]
+    rules: Rules,
+
+    /// Stops the simulation after the given number of steps
+    #[structopt(long)]
+    max_steps: Option<usize>,
+
+    /// Walks the guards together instead of one at a time, each one turning in front of the
+    /// others
+    #[structopt(long)]
+    together: bool,
+
+    /// Records every step of the guard, with the given rules and obstruction, to a JSON-lines
+    /// file instead of solving the puzzle
+    #[structopt(long, parse(from_os_str))]
+    trace: Option<PathBuf>,
+
+    /// Replays a trace recorded with --trace instead of solving the puzzle
+    #[structopt(long, parse(from_os_str))]
+    replay: Option<PathBuf>,
+
+    /// Step at which to show the state of the replayed trace, the last one by default
+    #[structopt(long)]
+    at: Option<usize>,
+
+    /// Other trace to compare the replayed one with
+    #[structopt(long, parse(from_os_str))]
+    diff: Option<PathBuf>,
+
+    /// File to read the map from, instead of stdin, when not solving the puzzle
+    #[structopt(parse(from_os_str))]
+    input: Option<PathBuf>,
+}
 
-use map::Direction;
-
-use self::map::Map;
-use self::map::Point;
-use self::map::Position;
-
-#[derive(Debug, Clone)]
-struct Guard {
-    current_position: Option<Position>,
-    previous_positions: HashSet<Position>,
-    previous_points: HashSet<Point>,
-    map: Map,
+fn parse_coordinate(s: &str) -> Result<Coordinate, Box<dyn Error>> {
+    let (row, col) = s.split_once(',').ok_or("expected \"row,column\"")?;
+    Ok(Coordinate::new(row.trim().parse()?, col.trim().parse()?))
 }
 
-impl Display for Guard {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for point in (0..self.map.width).flat_map(|line| {
-            (0..self.map.height).map(move |column| Point::new(line as isize, column as isize))
-        }) {
-            // Guard position
-            if let Some(position) = self
-                .current_position
-                .iter()
-                .find(|&&position| position.point == point)
-            {
-                write!(f, "{}", position.direction)?;
-            }
-            // Obstructions
-            else if self.map.is_point_obstructed(point) {
-                write!(f, "#")?;
-            }
-            // Starting position
-            else if self.map.guard_start_position() == point {
-                write!(f, "G")?;
-            }
-            // Past positions
-            else if let Some(position) = self
-                .previous_positions
-                .iter()
-                .find(|&&position| position == point)
-            {
-                write!(
-                    f,
-                    "{}",
-                    match position.direction {
-                        Direction::North | Direction::South => '|',
-                        Direction::East | Direction::West => '-',
-                    }
-                )?;
-            }
-            // Empty positions
-            else {
-                write!(f, ".")?;
-            }
-
-            // new line
-            if point.column == self.map.width as isize - 1 {
-                writeln!(f)?;
-            }
+fn read_input(opt: &Opt) -> Result<String, Box<dyn Error>> {
+    match &opt.input {
+        Some(path) => Ok(fs::read_to_string(path)?),
+        None => {
+            let mut map = String::new();
+            io::stdin().read_to_string(&mut map)?;
+            Ok(map)
         }
-
-        Ok(())
     }
 }
 
-#[derive(Debug, Clone)]
-enum GuardError {
-    LoopDetected,
+fn read_map(opt: &Opt) -> Result<Map, Box<dyn Error>> {
+    Ok(read_input(opt)?.parse()?)
 }
 
-impl Guard {
-    fn new(map: Map) -> Guard {
-        Guard {
-            current_position: Some(map.guard_start_position()),
-            previous_positions: HashSet::new(),
-            previous_points: HashSet::new(),
-            map,
-        }
-    }
+/// Reads the map and adds the obstruction given with --obstruction, if any.
+fn read_map_with_obstruction(opt: &Opt) -> Result<Map, Box<dyn Error>> {
+    let mut map = read_map(opt)?;
 
-    fn r#move(&mut self) -> Option<Position> {
-        if let Some(position) = self.current_position {
-            self.previous_positions.insert(position);
-            self.previous_points.insert(position.point);
-            self.current_position = {
-                let next_position = position.next();
-
-                if !self.map.is_point_on_grid(next_position.point) {
-                    None
-                } else if self.map.is_point_obstructed(next_position.point) {
-                    Some(position.rotate_clockwise())
-                } else {
-                    Some(next_position)
-                }
-            }
+    if let Some(obstruction) = opt.obstruction {
+        if !map.is_point_on_grid(obstruction) {
+            return Err(format!("obstruction {obstruction} is not on the map").into());
         }
-
-        self.current_position
-    }
-
-    fn move_until_out_of_grid(&mut self) -> Result<(), GuardError> {
-        while let Some(position) = self.r#move() {
-            if self.previous_positions.contains(&position) {
-                return Err(GuardError::LoopDetected);
-            }
+        if map.guard_start_position() == obstruction {
+            return Err(format!("obstruction {obstruction} is on the guard's start").into());
         }
-
-        Ok(())
+        map.add_obstruction(obstruction);
     }
+
+    Ok(map)
 }
 
-fn unique_points(map: Map) -> HashSet<Point> {
-    let mut guard = Guard::new(map);
-    let _ = guard.move_until_out_of_grid();
+fn animate(opt: &Opt) -> Result<(), Box<dyn Error>> {
+    if opt.step && opt.input.is_none() {
+        return Err("--step reads Enter from stdin, the map must be given as a file".into());
+    }
 
-    guard
-        .previous_positions
-        .iter()
-        .map(|p| p.point)
-        .collect::<HashSet<_>>()
-}
+    let map = read_map_with_obstruction(opt)?;
 
-fn possible_new_obstructions(map: Map) -> HashSet<Point> {
-    let mut guard = Guard::new(map);
-    let mut possible_new_obstructions = HashSet::new();
+    let animation = Animation {
+        delay: Duration::from_millis(opt.delay),
+        step_by_step: opt.step,
+    };
+    animation.play(map, &mut io::stdout(), &mut io::stdin().lock())?;
 
-    while let Some(position) = guard.current_position {
-        let next_point = position.next().point;
+    Ok(())
+}
 
-        if !guard.map.is_point_on_grid(next_point) {
-            break;
-        }
+fn record_trace(opt: &Opt, path: &PathBuf) -> Result<(), Box<dyn Error>> {
+    let map = read_map_with_obstruction(opt)?;
 
-        if !guard.map.is_point_obstructed(next_point)
-            && !guard.previous_points.contains(&next_point)
-        {
-            let mut second_guard = guard.clone();
-            second_guard.map.obstructions.insert(next_point);
-            if let Err(GuardError::LoopDetected) = second_guard.move_until_out_of_grid() {
-                possible_new_obstructions.insert(next_point);
-            }
-        }
+    let mut rules = opt.rules.clone();
+    rules.max_steps = opt.max_steps;
+    let start = map.guard_start_position();
+    let trace = Trace::record(&mut Guard::with_rules(start, map, rules));
 
-        guard.r#move();
-    }
+    trace.write_json_lines(BufWriter::new(fs::File::create(path)?))?;
+    println!("{} steps written to {}", trace.events.len(), path.display());
 
-    possible_new_obstructions
+    Ok(())
 }
 
-fn main() -> Result<(), Box<dyn std::error::Error>> {
-    println!("Hello, Advent of Code 2024!");
-    println!("--- Day 6 ---");
-
-    let mut input = String::new();
-    std::io::stdin().read_to_string(&mut input)?;
+fn read_trace(path: &PathBuf) -> Result<Trace, Box<dyn Error>> {
+    let file = fs::File::open(path)?;
+    Trace::read_json_lines(BufReader::new(file))
+        .map_err(|error| format!("{}: {error}", path.display()).into())
+}
 
-    let map: Map = input.parse()?;
+fn replay(opt: &Opt, path: &PathBuf) -> Result<(), Box<dyn Error>> {
+    let trace = read_trace(path)?;
 
-    let unique_points = unique_points(map.clone());
-    let new_obstructions = possible_new_obstructions(map.clone());
+    if let Some(other_path) = &opt.diff {
+        match trace.first_difference(&read_trace(other_path)?) {
+            Some(step) => println!("The traces differ from step {step}"),
+            None => println!("The traces are identical"),
+        }
+        return Ok(());
+    }
 
-    println!(
-        "Part 1: distinct points guard will visit: {}",
-        unique_points.len()
-    );
-    println!(
-        "Part 2: number of possible new obstructions: {}",
-        new_obstructions.len()
-    );
+    let step = opt.at.unwrap_or(trace.events.len());
+    print!("{}", trace.guard_at(step));
+    match trace.position_at(step) {
+        Some(position) => println!("Step {step}: guard at {position}"),
+        None => println!("Step {step}: the guard has left the map"),
+    }
+    if let Some(event) = step
+        .checked_sub(1)
+        .and_then(|index| trace.events.get(index))
+    {
+        println!("Last event: {event}");
+    }
+    println!("Points visited: {}", trace.visited_points_at(step).len());
 
     Ok(())
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+fn simulate(opt: &Opt) -> Result<(), Box<dyn Error>> {
+    let map = Map::parse_multiple_guards(&read_input(opt)?)?;
+    let mut rules = opt.rules.clone();
+    rules.max_steps = opt.max_steps;
 
-    use crate::map::Direction;
-    use crate::map::Point;
+    if opt.together {
+        match aoc2406::simulate_together(&map, &rules) {
+            Ok(steps) => println!("The guards left the map after {steps} steps"),
+            Err(error) => println!("The guards stopped: {error}"),
+        }
+        return Ok(());
+    }
 
-    const SAMPLE: &str = include_str!("../sample.txt");
+    for (start, outcome) in map.guards().zip(aoc2406::simulate(&map, &rules)) {
+        match outcome {
+            Ok(steps) => println!("Guard at {start}: left the map after {steps} steps"),
+            Err(error) => println!("Guard at {start}: {error}"),
+        }
+    }
 
-    #[test]
-    fn test_guard_initial_position() {
-        let map: Map = SAMPLE.parse().unwrap();
-        let initial_position = Position {
-            point: Point::new(6, 4),
-            direction: Direction::North,
-        };
+    Ok(())
+}
 
-        let guard = Guard::new(map);
+fn main() -> Result<(), Box<dyn Error>> {
+    let opt = Opt::from_args();
 
-        assert_eq!(guard.current_position, Some(initial_position));
-        assert_eq!(guard.previous_points.len(), 0);
-        assert_eq!(guard.previous_positions.len(), 0);
+    if opt.animate {
+        return animate(&opt);
     }
 
-    #[test]
-    fn test_guard_move() {
-        let map: Map = SAMPLE.parse().unwrap();
-        let mut guard = Guard::new(map);
-        let expected_position = Position {
-            point: Point::new(5, 4),
-            direction: Direction::North,
-        };
-        let previous_position = guard.current_position.unwrap();
-
-        let new_position = guard.r#move().unwrap();
-
-        assert_eq!(new_position, expected_position);
-        assert_eq!(guard.previous_points.len(), 1);
-        assert!(guard.previous_points.contains(&previous_position.point));
-        assert_eq!(guard.previous_positions.len(), 1);
-        assert!(guard.previous_positions.contains(&previous_position));
+    if opt.simulate {
+        return simulate(&opt);
     }
 
-    #[test]
-    fn test_count_unique_points() {
-        let map: Map = SAMPLE.parse().unwrap();
+    if let Some(path) = &opt.trace {
+        return record_trace(&opt, path);
+    }
 
-        let unique_points = unique_points(map);
+    if let Some(path) = &opt.replay {
+        return replay(&opt, path);
+    }
 
-        assert_eq!(unique_points.len(), 41);
+    if opt.loops {
+        for (obstruction, cycle) in aoc2406::new_obstruction_loops(&read_map(&opt)?)? {
+            println!("Obstruction at {obstruction}: loop {cycle}");
+        }
+        return Ok(());
     }
 
-    #[test]
-    fn test_possible_new_obstructions() {
-        let map: Map = SAMPLE.parse().unwrap();
-
-        let obstructions = dbg!(possible_new_obstructions(map));
-
-        assert_eq!(obstructions.len(), 6);
-        assert_eq!(
-            obstructions,
-            HashSet::from([
-                Point::new(6, 3),
-                Point::new(7, 6),
-                Point::new(7, 7),
-                Point::new(8, 1),
-                Point::new(8, 3),
-                Point::new(9, 7),
-            ])
-        );
+    if let Some(path) = &opt.image {
+        aoc2406::render(&read_map(&opt)?).save(path)?;
+        println!("Image written to {}", path.display());
+        return Ok(());
     }
+
+    println!("Hello, Advent of Code 2024!");
+    println!("--- Day 6 ---");
+
+    aoc_common::solve_stdin::<aoc2406::Day06>()
 }
@@ -1,234 +1,281 @@
-use std::collections::HashSet;
+use std::error::Error;
 use std::fmt::Display;
 use std::str::FromStr;
 
+use aoc_grid::Coordinate;
+use aoc_grid::Direction;
+use aoc_grid::SparseGrid;
+
 #[derive(Debug, Default, Clone, Copy, Hash, Eq, PartialEq)]
-pub struct Point {
-    line: isize,
-    column: isize,
+pub struct Position {
+    pub point: Coordinate,
+    pub direction: Direction,
 }
 
-impl Point {
-    pub fn new(line: isize, column: isize) -> Self {
-        Point { line, column }
+impl Position {
+    /// Next position towards the current direction, if its point is not out of range.
+    pub fn next(&self) -> Option<Self> {
+        Some(Position {
+            point: self.point.next_towards(self.direction)?,
+            direction: self.direction,
+        })
     }
 
-    pub fn next_towards(&self, direction: Direction) -> Point {
-        match direction {
-            Direction::North => Point {
-                line: self.line - 1,
-                column: self.column,
-            },
-            Direction::South => Point {
-                line: self.line + 1,
-                column: self.column,
-            },
-            Direction::East => Point {
-                line: self.line,
-                column: self.column + 1,
-            },
-            Direction::West => Point {
-                line: self.line,
-                column: self.column - 1,
-            },
+    pub fn rotate_clockwise(&self) -> Self {
+        Position {
+            point: self.point,
+            direction: self.direction.rotate_clockwise(),
         }
     }
 }
 
-impl Display for Point {
+impl Display for Position {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "({}, {})", self.line, self.column)
+        write!(f, "{} {}", self.point, self.direction)
     }
 }
 
-impl From<(isize, isize)> for Point {
-    fn from((line, column): (isize, isize)) -> Self {
-        Point::new(line, column)
+impl PartialEq<Coordinate> for Position {
+    fn eq(&self, other: &Coordinate) -> bool {
+        self.point == *other
     }
 }
 
-impl From<(usize, usize)> for Point {
-    fn from((line, column): (usize, usize)) -> Self {
-        Point::new(line as isize, column as isize)
-    }
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Map {
+    pub obstructions: SparseGrid<()>,
+    /// Starting position of the first guard in reading order, the only one in the puzzle.
+    guard_start: Position,
+    /// Starting positions of the other guards, in reading order.
+    other_guards: Vec<Position>,
 }
 
-#[derive(Debug, Default, Clone, Copy, Hash, Eq, PartialEq)]
-pub enum Direction {
-    #[default]
-    North,
-    South,
-    East,
-    West,
+/// Why a map could not be parsed. Lines and columns are counted from 1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MapError {
+    Empty,
+    MissingGuard,
+    MultipleGuards {
+        first_line: usize,
+        first_column: usize,
+        second_line: usize,
+        second_column: usize,
+    },
+    UnknownGlyph {
+        line: usize,
+        column: usize,
+        glyph: char,
+    },
+    RaggedRow {
+        line: usize,
+        expected_width: usize,
+        actual_width: usize,
+    },
 }
 
-impl Display for Direction {
+impl Display for MapError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "{}",
-            match self {
-                Direction::North => '^',
-                Direction::South => 'v',
-                Direction::East => '>',
-                Direction::West => '<',
-            }
-        )
-    }
-}
-
-impl Direction {
-    pub fn rotate_clockwise(&self) -> Direction {
         match self {
-            Direction::North => Direction::East,
-            Direction::East => Direction::South,
-            Direction::South => Direction::West,
-            Direction::West => Direction::North,
+            MapError::Empty => write!(f, "Map is empty"),
+            MapError::MissingGuard => write!(f, "Map has no guard"),
+            MapError::MultipleGuards {
+                first_line,
+                first_column,
+                second_line,
+                second_column,
+            } => write!(
+                f,
+                "Map has several guards, at line {first_line}, column {first_column} \
+                 and line {second_line}, column {second_column}"
+            ),
+            MapError::UnknownGlyph {
+                line,
+                column,
+                glyph,
+            } => write!(f, "Unknown glyph {glyph:?} at line {line}, column {column}"),
+            MapError::RaggedRow {
+                line,
+                expected_width,
+                actual_width,
+            } => write!(
+                f,
+                "Row at line {line} is {actual_width} wide, expected {expected_width}"
+            ),
         }
     }
 }
 
-impl TryFrom<char> for Direction {
-    type Error = Box<dyn std::error::Error>;
-
-    fn try_from(s: char) -> Result<Self, Self::Error> {
-        match s {
-            '^' => Ok(Direction::North),
-            'v' => Ok(Direction::South),
-            '>' => Ok(Direction::East),
-            '<' => Ok(Direction::West),
-            _ => Err("Invalid direction".into()),
-        }
-    }
-}
+impl Error for MapError {}
 
-#[derive(Debug, Default, Clone, Copy, Hash, Eq, PartialEq)]
-pub struct Position {
-    pub point: Point,
-    pub direction: Direction,
-}
-
-impl Position {
-    pub fn next(&self) -> Self {
-        Position {
-            point: self.point.next_towards(self.direction),
-            direction: self.direction,
-        }
-    }
+/// Parses a map with exactly one guard, as in the puzzle.
+impl FromStr for Map {
+    type Err = MapError;
 
-    pub fn rotate_clockwise(&self) -> Self {
-        Position {
-            point: self.point,
-            direction: self.direction.rotate_clockwise(),
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let map = Map::parse_multiple_guards(s)?;
+
+        match map.other_guards.first() {
+            None => Ok(map),
+            Some(second) => Err(MapError::MultipleGuards {
+                first_line: map.guard_start.point.row + 1,
+                first_column: map.guard_start.point.col + 1,
+                second_line: second.point.row + 1,
+                second_column: second.point.col + 1,
+            }),
         }
     }
 }
 
-impl PartialEq<Point> for Position {
-    fn eq(&self, other: &Point) -> bool {
-        self.point == *other
-    }
-}
-
-#[derive(Debug, Default, Clone)]
-pub struct Map {
-    pub obstructions: HashSet<Point>,
-    pub guard_start: Position,
-    pub height: usize,
-    pub width: usize,
-}
-
-impl FromStr for Map {
-    type Err = Box<dyn std::error::Error>;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut obstructions = HashSet::new();
-        let mut guard_start = Position::default();
+impl Map {
+    /// Parses a map with one or more guards.
+    pub fn parse_multiple_guards(s: &str) -> Result<Self, MapError> {
+        let mut guards = Vec::new();
+        let mut obstructions = Vec::new();
+        let mut width = None;
         let mut height = 0;
-        let mut width = 0;
 
-        for (line, line_elems) in s.lines().enumerate() {
+        for (row, line) in s.lines().enumerate() {
             height += 1;
-
             let mut line_width = 0;
 
-            for (column, elem) in line_elems.chars().enumerate() {
+            for (col, glyph) in line.chars().enumerate() {
                 line_width += 1;
-
-                if elem == '#' {
-                    obstructions.insert(Point::from((line, column)));
-                } else if let Ok(direction) = elem.try_into() {
-                    guard_start = Position {
-                        point: Point::from((line, column)),
-                        direction,
-                    }
+                let point = Coordinate::new(row, col);
+
+                match glyph {
+                    '.' => {}
+                    '#' => obstructions.push(point),
+                    glyph => match Direction::ORTHOGONAL
+                        .into_iter()
+                        .find(|&direction| char::from(direction) == glyph)
+                    {
+                        Some(direction) => guards.push(Position { point, direction }),
+                        None => {
+                            return Err(MapError::UnknownGlyph {
+                                line: row + 1,
+                                column: col + 1,
+                                glyph,
+                            })
+                        }
+                    },
                 }
             }
 
-            if width == 0 {
-                width = line_width;
-            } else if width != line_width {
-                return Err("Map is not rectangular".into());
+            let expected_width = *width.get_or_insert(line_width);
+            if line_width != expected_width {
+                return Err(MapError::RaggedRow {
+                    line: row + 1,
+                    expected_width,
+                    actual_width: line_width,
+                });
             }
         }
 
-        Ok(Map {
-            obstructions,
+        let width = width.filter(|&width| width > 0).ok_or(MapError::Empty)?;
+        let mut guards = guards.into_iter();
+        let guard_start = guards.next().ok_or(MapError::MissingGuard)?;
+
+        let mut map = Map {
+            obstructions: SparseGrid::new(height, width),
             guard_start,
-            height,
-            width,
-        })
+            other_guards: guards.collect(),
+        };
+        obstructions
+            .into_iter()
+            .for_each(|point| map.add_obstruction(point));
+
+        Ok(map)
+    }
+}
+
+/// Writes the map back in the format it is parsed from.
+impl Display for Map {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in 0..self.height() {
+            for col in 0..self.width() {
+                let point = Coordinate::new(row, col);
+
+                if let Some(guard) = self.guards().find(|&guard| guard == point) {
+                    write!(f, "{}", guard.direction)?;
+                } else if self.is_point_obstructed(point) {
+                    write!(f, "#")?;
+                } else {
+                    write!(f, ".")?;
+                }
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
     }
 }
 
 impl Map {
+    pub fn height(&self) -> usize {
+        self.obstructions.height()
+    }
+
+    pub fn width(&self) -> usize {
+        self.obstructions.width()
+    }
+
+    /// Starting position of the first guard, the only one in the puzzle.
     pub fn guard_start_position(&self) -> Position {
         self.guard_start
     }
 
-    pub fn is_point_obstructed(&self, point: Point) -> bool {
-        self.obstructions.contains(&point)
+    /// Starting positions of all the guards, in reading order.
+    pub fn guards(&self) -> impl Iterator<Item = Position> + '_ {
+        std::iter::once(self.guard_start).chain(self.other_guards.iter().copied())
     }
 
-    pub fn is_point_on_grid(&self, point: Point) -> bool {
-        (0..self.height as isize).contains(&point.line)
-            && (0..self.width as isize).contains(&point.column)
+    pub fn add_obstruction(&mut self, point: Coordinate) {
+        self.obstructions.insert(point, ());
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    pub fn is_point_obstructed(&self, point: Coordinate) -> bool {
+        self.obstructions.contains(point)
+    }
 
-    #[test]
-    fn test_point_move_to() {
-        let point = Point::new(0, 0);
-        assert_eq!(point.next_towards(Direction::North), Point::new(-1, 0));
-        assert_eq!(point.next_towards(Direction::South), Point::new(1, 0));
-        assert_eq!(point.next_towards(Direction::East), Point::new(0, 1));
-        assert_eq!(point.next_towards(Direction::West), Point::new(0, -1));
+    pub fn is_point_on_grid(&self, point: Coordinate) -> bool {
+        self.obstructions.is_on_grid(point)
     }
 
-    #[test]
-    fn test_direction_rotate_clockwise() {
-        assert_eq!(Direction::North.rotate_clockwise(), Direction::East);
-        assert_eq!(Direction::East.rotate_clockwise(), Direction::South);
-        assert_eq!(Direction::South.rotate_clockwise(), Direction::West);
-        assert_eq!(Direction::West.rotate_clockwise(), Direction::North);
+    /// Next position towards the current direction, if it is on the grid.
+    pub fn next_position(&self, position: Position) -> Option<Position> {
+        position
+            .next()
+            .filter(|next| self.is_point_on_grid(next.point))
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
 
     #[test]
-    fn test_char_to_direction() {
-        assert_eq!(Direction::try_from('^').unwrap(), Direction::North);
-        assert_eq!(Direction::try_from('v').unwrap(), Direction::South);
-        assert_eq!(Direction::try_from('>').unwrap(), Direction::East);
-        assert_eq!(Direction::try_from('<').unwrap(), Direction::West);
+    fn test_position_next() {
+        let position = Position {
+            point: Coordinate::new(1, 1),
+            direction: Direction::West,
+        };
+
+        assert_eq!(position.next().unwrap().point, Coordinate::new(1, 0));
+        assert_eq!(position.next().unwrap().direction, Direction::West);
+        assert_eq!(position.next().unwrap().next(), None);
     }
 
     #[test]
-    fn test_invalid_char_to_direction() {
-        assert!(Direction::try_from('#').is_err());
-        assert!(Direction::try_from('.').is_err());
+    fn test_position_rotate_clockwise() {
+        let position = Position {
+            point: Coordinate::new(1, 1),
+            direction: Direction::West,
+        };
+
+        assert_eq!(position.rotate_clockwise().point, Coordinate::new(1, 1));
+        assert_eq!(position.rotate_clockwise().direction, Direction::North);
     }
 
     #[test]
@@ -239,19 +286,210 @@ mod tests {
 
         let map: Map = map.parse().unwrap();
 
-        assert_eq!(map.height, 3);
-        assert_eq!(map.width, 3);
-        assert_eq!(map.guard_start.point.line, 0);
-        assert_eq!(map.guard_start.point.column, 2);
-        assert_eq!(map.guard_start.direction, Direction::South);
+        assert_eq!(map.height(), 3);
+        assert_eq!(map.width(), 3);
+        assert_eq!(map.guards().count(), 1);
+        assert_eq!(map.guard_start_position().point.row, 0);
+        assert_eq!(map.guard_start_position().point.col, 2);
+        assert_eq!(map.guard_start_position().direction, Direction::South);
         assert_eq!(map.obstructions.len(), 3);
+        assert!(map.is_point_obstructed(Coordinate::new(0, 1)));
+        assert!(map.is_point_obstructed(Coordinate::new(1, 0)));
+        assert!(map.is_point_obstructed(Coordinate::new(2, 2)));
+        assert!(map.is_point_on_grid(Coordinate::new(0, 1)));
+        assert!(!map.is_point_on_grid(Coordinate::new(3, 3)));
+    }
+
+    #[test]
+    fn test_parse_map_with_several_guards() {
+        let map = Map::parse_multiple_guards(
+            ".>.\n\
+             #..\n\
+             ^.<\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            map.guards().collect::<Vec<_>>(),
+            vec![
+                Position {
+                    point: Coordinate::new(0, 1),
+                    direction: Direction::East,
+                },
+                Position {
+                    point: Coordinate::new(2, 0),
+                    direction: Direction::North,
+                },
+                Position {
+                    point: Coordinate::new(2, 2),
+                    direction: Direction::West,
+                },
+            ]
+        );
+        assert_eq!(map.guard_start_position(), map.guards().next().unwrap());
+        assert_eq!(map.to_string(), ".>.\n#..\n^.<\n");
+    }
+
+    #[test]
+    fn test_next_position() {
+        let map: Map = "..\n.<".parse().unwrap();
+        let position = map.guard_start_position();
+
+        let next = map.next_position(position).unwrap();
+        assert_eq!(next.point, Coordinate::new(1, 0));
+        assert_eq!(map.next_position(next), None);
+        assert_eq!(
+            map.next_position(Position {
+                point: Coordinate::new(1, 0),
+                direction: Direction::South,
+            }),
+            None
+        );
+    }
+
+    #[test]
+    fn test_display_map() {
+        let map = "..#.\n\
+                   #...\n\
+                   ..>#\n";
+
+        assert_eq!(map.parse::<Map>().unwrap().to_string(), map);
+    }
+
+    /// Maps of random sizes and obstructions, with the guard anywhere not obstructed.
+    fn map_strategy() -> impl Strategy<Value = String> {
+        (1..20usize, 1..20usize)
+            .prop_flat_map(|(height, width)| {
+                (
+                    prop::collection::vec(prop::bool::weighted(0.2), height * width),
+                    0..height * width,
+                    prop::sample::select(Direction::ORTHOGONAL.to_vec()),
+                    Just(width),
+                )
+            })
+            .prop_map(|(obstructions, guard, direction, width)| {
+                let cells = obstructions
+                    .into_iter()
+                    .enumerate()
+                    .map(|(index, obstructed)| match index {
+                        index if index == guard => direction.to_string(),
+                        _ if obstructed => "#".to_string(),
+                        _ => ".".to_string(),
+                    })
+                    .collect::<Vec<_>>();
+
+                cells.chunks(width).map(|row| row.concat() + "\n").collect()
+            })
+    }
+
+    proptest! {
+        #[test]
+        fn test_display_parse_round_trip(input in map_strategy()) {
+            let map: Map = input.parse().unwrap();
+
+            prop_assert_eq!(map.to_string(), input);
+            prop_assert_eq!(map.to_string().parse::<Map>().unwrap(), map);
+        }
+    }
+
+    #[test]
+    fn test_parse_map_not_rectangular() {
+        assert_eq!(
+            "..#\n.^\n".parse::<Map>(),
+            Err(MapError::RaggedRow {
+                line: 2,
+                expected_width: 3,
+                actual_width: 2,
+            })
+        );
+        assert_eq!(
+            ".^\n...\n".parse::<Map>(),
+            Err(MapError::RaggedRow {
+                line: 2,
+                expected_width: 2,
+                actual_width: 3,
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_map_empty() {
+        assert_eq!("".parse::<Map>(), Err(MapError::Empty));
+        assert_eq!("\n".parse::<Map>(), Err(MapError::Empty));
+    }
+
+    #[test]
+    fn test_parse_map_missing_guard() {
+        assert_eq!("..#\n...\n".parse::<Map>(), Err(MapError::MissingGuard));
         assert_eq!(
-            map.obstructions,
-            HashSet::from([Point::new(0, 1), Point::new(1, 0), Point::new(2, 2)])
+            Map::parse_multiple_guards("..#\n...\n"),
+            Err(MapError::MissingGuard)
+        );
+    }
+
+    #[test]
+    fn test_parse_map_multiple_guards() {
+        assert_eq!(
+            ".>#\n..^\n<..".parse::<Map>(),
+            Err(MapError::MultipleGuards {
+                first_line: 1,
+                first_column: 2,
+                second_line: 2,
+                second_column: 3,
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_map_unknown_glyph() {
+        assert_eq!(
+            "..#\n.^.\n.O.".parse::<Map>(),
+            Err(MapError::UnknownGlyph {
+                line: 3,
+                column: 2,
+                glyph: 'O',
+            })
+        );
+        // guards only face along rows and columns
+        assert_eq!(
+            ".↗.\n...".parse::<Map>(),
+            Err(MapError::UnknownGlyph {
+                line: 1,
+                column: 2,
+                glyph: '↗',
+            })
+        );
+    }
+
+    #[test]
+    fn test_map_error_display() {
+        assert_eq!(
+            MapError::RaggedRow {
+                line: 4,
+                expected_width: 10,
+                actual_width: 9,
+            }
+            .to_string(),
+            "Row at line 4 is 9 wide, expected 10"
+        );
+        assert_eq!(
+            MapError::UnknownGlyph {
+                line: 1,
+                column: 7,
+                glyph: 'x',
+            }
+            .to_string(),
+            "Unknown glyph 'x' at line 1, column 7"
+        );
+        assert_eq!(
+            MapError::MultipleGuards {
+                first_line: 1,
+                first_column: 2,
+                second_line: 2,
+                second_column: 3,
+            }
+            .to_string(),
+            "Map has several guards, at line 1, column 2 and line 2, column 3"
         );
-        assert!(map.is_point_obstructed(Point::new(0, 1)));
-        assert!(map.is_point_on_grid(Point::new(0, 1)));
-        assert!(!map.is_point_on_grid(Point::new(3, 3)));
-        assert!(!map.is_point_on_grid(Point::new(-1, -1)));
     }
 }
@@ -0,0 +1,155 @@
+use std::collections::HashMap;
+
+use crate::map::Map;
+use crate::rules::Rules;
+use crate::Guard;
+use crate::GuardError;
+
+/// Walks each guard of the map alone, as if the others were not there, returning how many
+/// steps each one takes to leave the map.
+pub fn simulate(map: &Map, rules: &Rules) -> Vec<Result<usize, GuardError>> {
+    map.guards()
+        .map(|start| {
+            let mut guard = Guard::with_rules(start, map.clone(), rules.clone());
+            guard.move_until_out_of_grid()?;
+            Ok(guard.path.len())
+        })
+        .collect()
+}
+
+/// Walks all guards of the map together, returning how many steps it takes for all of them to
+/// leave the map.
+///
+/// At each step, the guards still on the map move one after the other in reading order of their
+/// starting positions, each one turning in front of the others as it does in front of
+/// obstructions.
+pub fn simulate_together(map: &Map, rules: &Rules) -> Result<usize, GuardError> {
+    let mut guards = map
+        .guards()
+        .map(|start| Guard::with_rules(start, map.clone(), rules.clone()))
+        .collect::<Vec<_>>();
+    let mut previous_states = HashMap::new();
+    let mut steps = 0;
+
+    loop {
+        let state = guards.iter().map(Guard::state).collect::<Vec<_>>();
+        if state.iter().all(Option::is_none) {
+            return Ok(steps);
+        }
+        if let Some(entry_step) = previous_states.insert(state, steps) {
+            return Err(GuardError::PatrolLoopDetected {
+                entry_step,
+                length: steps - entry_step,
+            });
+        }
+        if rules.max_steps.is_some_and(|max_steps| steps >= max_steps) {
+            return Err(GuardError::StepLimitReached(steps));
+        }
+
+        for index in 0..guards.len() {
+            let others = guards
+                .iter()
+                .enumerate()
+                .filter(|&(other, _)| other != index)
+                .filter_map(|(_, guard)| guard.current_position)
+                .map(|position| position.point)
+                .collect();
+
+            guards[index].blocked = others;
+            guards[index].r#move();
+        }
+
+        steps += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::Turn;
+    use crate::Loop;
+
+    const SAMPLE: &str = include_str!("../sample.txt");
+
+    #[test]
+    fn test_simulate_sample() {
+        let map: Map = SAMPLE.parse().unwrap();
+
+        assert_eq!(simulate(&map, &Rules::default()), vec![Ok(55)]);
+        assert_eq!(
+            simulate(&map, &Rules::default().with_max_steps(10)),
+            vec![Err(GuardError::StepLimitReached(10))]
+        );
+    }
+
+    #[test]
+    fn test_simulate_with_rules() {
+        let map: Map = "..#\n\
+                        ...\n\
+                        ..^"
+        .parse()
+        .unwrap();
+
+        assert_eq!(simulate(&map, &Rules::clockwise()), vec![Ok(3)]);
+        assert_eq!(simulate(&map, &Rules::counter_clockwise()), vec![Ok(5)]);
+        assert_eq!(simulate(&map, &Rules::reverse()), vec![Ok(4)]);
+        assert_eq!(
+            simulate(&map, &Rules::sequence(vec![Turn::Back, Turn::Right])),
+            vec![Ok(4)]
+        );
+    }
+
+    #[test]
+    fn test_simulate_reverse_loop() {
+        let map: Map = ".#.\n\
+                        .^.\n\
+                        .#."
+        .parse()
+        .unwrap();
+
+        let [Err(GuardError::LoopDetected(Loop {
+            entry_step,
+            positions,
+        }))] = &simulate(&map, &Rules::reverse())[..]
+        else {
+            panic!("the guard should walk in a loop");
+        };
+        assert_eq!(*entry_step, 0);
+        assert_eq!(positions.len(), 2);
+    }
+
+    #[test]
+    fn test_simulate_several_guards() {
+        let map = Map::parse_multiple_guards(
+            "..\n\
+             ><",
+        )
+        .unwrap();
+
+        assert_eq!(simulate(&map, &Rules::default()), vec![Ok(2), Ok(2)]);
+        assert_eq!(simulate_together(&map, &Rules::default()), Ok(3));
+        assert_eq!(
+            simulate_together(&map, &Rules::default().with_max_steps(2)),
+            Err(GuardError::StepLimitReached(2))
+        );
+    }
+
+    #[test]
+    fn test_simulate_together_loop() {
+        let map = Map::parse_multiple_guards(
+            ".#.\n\
+             .^.\n\
+             .v.\n\
+             .#.",
+        )
+        .unwrap();
+
+        assert_eq!(
+            simulate_together(&map, &Rules::reverse()),
+            Err(GuardError::PatrolLoopDetected {
+                entry_step: 0,
+                length: 2,
+            })
+        );
+    }
+}
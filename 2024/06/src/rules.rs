@@ -0,0 +1,166 @@
+use std::fmt::Display;
+use std::str::FromStr;
+
+use aoc_grid::Direction;
+
+/// How the guard turns when facing an obstruction.
+#[derive(Debug, Clone, Copy, Hash, Eq, PartialEq)]
+pub enum Turn {
+    /// 90 degrees clockwise.
+    Right,
+    /// 90 degrees counter-clockwise.
+    Left,
+    /// Half turn.
+    Back,
+}
+
+impl Turn {
+    pub fn apply(&self, direction: Direction) -> Direction {
+        match self {
+            Turn::Right => direction.rotate_clockwise(),
+            Turn::Left => direction.rotate_counter_clockwise(),
+            Turn::Back => direction.opposite(),
+        }
+    }
+}
+
+impl Display for Turn {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Turn::Right => write!(f, "right"),
+            Turn::Left => write!(f, "left"),
+            Turn::Back => write!(f, "back"),
+        }
+    }
+}
+
+impl FromStr for Turn {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "right" => Ok(Turn::Right),
+            "left" => Ok(Turn::Left),
+            "back" => Ok(Turn::Back),
+            other => Err(format!(
+                "unknown turn \"{other}\", expected right, left or back"
+            )),
+        }
+    }
+}
+
+/// Behaviour of the guards: the turns they take on obstructions and how long they patrol.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Rules {
+    /// Turns taken on successive obstructions, starting over after the last one.
+    turns: Vec<Turn>,
+    /// Number of moves after which a guard stops, if any.
+    pub max_steps: Option<usize>,
+}
+
+/// The rules of the puzzle: always turning right, without limit.
+impl Default for Rules {
+    fn default() -> Self {
+        Rules::sequence(vec![Turn::Right])
+    }
+}
+
+impl Rules {
+    pub fn clockwise() -> Self {
+        Rules::sequence(vec![Turn::Right])
+    }
+
+    pub fn counter_clockwise() -> Self {
+        Rules::sequence(vec![Turn::Left])
+    }
+
+    pub fn reverse() -> Self {
+        Rules::sequence(vec![Turn::Back])
+    }
+
+    /// Takes the given turns on successive obstructions, starting over after the last one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are no turns.
+    pub fn sequence(turns: Vec<Turn>) -> Self {
+        assert!(!turns.is_empty(), "guards need at least one turn");
+
+        Rules {
+            turns,
+            max_steps: None,
+        }
+    }
+
+    pub fn with_max_steps(mut self, max_steps: usize) -> Self {
+        self.max_steps = Some(max_steps);
+        self
+    }
+
+    pub fn turns(&self) -> &[Turn] {
+        &self.turns
+    }
+
+    /// Turn to take on the obstruction met after `turns_taken` previous turns.
+    pub fn turn(&self, turns_taken: usize) -> Turn {
+        self.turns[turns_taken % self.turns.len()]
+    }
+}
+
+/// Parses a comma separated sequence of turns, like "right" or "left,left,back", or one of
+/// "clockwise", "counter-clockwise" and "reverse".
+impl FromStr for Rules {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "clockwise" => Ok(Rules::clockwise()),
+            "counter-clockwise" => Ok(Rules::counter_clockwise()),
+            "reverse" => Ok(Rules::reverse()),
+            "" => Err("expected at least one turn".to_string()),
+            turns => Ok(Rules::sequence(
+                turns.split(',').map(str::parse).collect::<Result<_, _>>()?,
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_turn_apply() {
+        assert_eq!(Turn::Right.apply(Direction::North), Direction::East);
+        assert_eq!(Turn::Left.apply(Direction::North), Direction::West);
+        assert_eq!(Turn::Back.apply(Direction::North), Direction::South);
+    }
+
+    #[test]
+    fn test_rules_turn_sequence() {
+        let rules = Rules::sequence(vec![Turn::Right, Turn::Right, Turn::Left]);
+
+        assert_eq!(
+            (0..5).map(|turns| rules.turn(turns)).collect::<Vec<_>>(),
+            vec![
+                Turn::Right,
+                Turn::Right,
+                Turn::Left,
+                Turn::Right,
+                Turn::Right
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_rules() {
+        assert_eq!("clockwise".parse(), Ok(Rules::default()));
+        assert_eq!("reverse".parse(), Ok(Rules::reverse()));
+        assert_eq!(
+            "left, left,back".parse(),
+            Ok(Rules::sequence(vec![Turn::Left, Turn::Left, Turn::Back]))
+        );
+        assert!("left,up".parse::<Rules>().is_err());
+        assert!("".parse::<Rules>().is_err());
+    }
+}
@@ -0,0 +1,156 @@
+use std::fmt::Display;
+
+use crate::map::Position;
+use crate::Guard;
+use crate::GuardError;
+use crate::Loop;
+
+/// What happened to a guard during one step.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StepEvent {
+    /// The guard walked forward to the given position.
+    Moved(Position),
+    /// The guard faced an obstruction and turned in place.
+    Turned(Position),
+    /// The guard walked out of the map.
+    LeftGrid,
+    /// The guard came back to a position it had already left, and will walk this loop forever.
+    LoopDetected(Loop),
+    /// The guard was still on the map after the given number of steps, the limit of its rules.
+    StepLimitReached(usize),
+}
+
+impl Display for StepEvent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StepEvent::Moved(position) => write!(f, "moved to {position}"),
+            StepEvent::Turned(position) => write!(f, "turned to {position}"),
+            StepEvent::LeftGrid => write!(f, "left the map"),
+            StepEvent::LoopDetected(cycle) => write!(f, "loop detected, {cycle}"),
+            StepEvent::StepLimitReached(steps) => write!(f, "stopped after {steps} steps"),
+        }
+    }
+}
+
+/// Iterator over the steps of a guard, see [`Guard::steps`].
+///
+/// The last event is always [`StepEvent::LeftGrid`], [`StepEvent::LoopDetected`] or
+/// [`StepEvent::StepLimitReached`].
+#[derive(Debug)]
+pub struct Steps<'g> {
+    guard: &'g mut Guard,
+    finished: bool,
+}
+
+impl<'g> Steps<'g> {
+    pub(crate) fn new(guard: &'g mut Guard) -> Self {
+        Steps {
+            finished: guard.current_position.is_none(),
+            guard,
+        }
+    }
+}
+
+impl Iterator for Steps<'_> {
+    type Item = StepEvent;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+
+        if self.guard.step_limit_reached() {
+            self.finished = true;
+            return Some(StepEvent::StepLimitReached(self.guard.steps_taken()));
+        }
+
+        let from = self.guard.current_position?;
+        let event = match self.guard.r#move() {
+            None => StepEvent::LeftGrid,
+            Some(to) => match self.guard.detect_loop() {
+                Err(GuardError::LoopDetected(cycle)) => StepEvent::LoopDetected(cycle),
+                _ if to.point == from.point => StepEvent::Turned(to),
+                _ => StepEvent::Moved(to),
+            },
+        };
+
+        self.finished = matches!(event, StepEvent::LeftGrid | StepEvent::LoopDetected(_));
+        Some(event)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_grid::Coordinate;
+    use aoc_grid::Direction;
+
+    use super::*;
+    use crate::map::Map;
+    use crate::rules::Rules;
+
+    fn position(row: usize, col: usize, direction: Direction) -> Position {
+        Position {
+            point: Coordinate::new(row, col),
+            direction,
+        }
+    }
+
+    #[test]
+    fn test_steps_until_left_grid() {
+        let map: Map = "..#\n\
+                        ...\n\
+                        ..^"
+        .parse()
+        .unwrap();
+        let mut guard = Guard::new(map);
+
+        assert_eq!(
+            guard.steps().collect::<Vec<_>>(),
+            vec![
+                StepEvent::Moved(position(1, 2, Direction::North)),
+                StepEvent::Turned(position(1, 2, Direction::East)),
+                StepEvent::LeftGrid,
+            ]
+        );
+        assert_eq!(guard.position(), None);
+        assert_eq!(guard.steps().next(), None);
+    }
+
+    #[test]
+    fn test_steps_until_loop() {
+        let map: Map = ".#.\n\
+                        .^.\n\
+                        .#."
+        .parse()
+        .unwrap();
+        let start = map.guard_start_position();
+        let mut guard = Guard::with_rules(start, map, Rules::reverse());
+
+        assert_eq!(
+            guard.steps().collect::<Vec<_>>(),
+            vec![
+                StepEvent::Turned(position(1, 1, Direction::South)),
+                StepEvent::LoopDetected(Loop {
+                    entry_step: 0,
+                    positions: vec![
+                        position(1, 1, Direction::North),
+                        position(1, 1, Direction::South),
+                    ],
+                }),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_steps_until_step_limit() {
+        let map: Map = include_str!("../sample.txt").parse().unwrap();
+        let start = map.guard_start_position();
+        let mut guard = Guard::with_rules(start, map, Rules::default().with_max_steps(3));
+
+        let events = guard.steps().collect::<Vec<_>>();
+
+        assert_eq!(events.len(), 4);
+        assert_eq!(events.last(), Some(&StepEvent::StepLimitReached(3)));
+        assert_eq!(guard.position(), Some(position(3, 4, Direction::North)));
+    }
+}
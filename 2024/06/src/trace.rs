@@ -0,0 +1,520 @@
+use std::collections::HashSet;
+use std::error::Error;
+use std::fmt::Display;
+use std::io;
+use std::io::BufRead;
+use std::io::Write;
+
+use aoc_grid::Coordinate;
+use aoc_grid::Direction;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::map::Map;
+use crate::map::Position;
+use crate::rules::Rules;
+use crate::rules::Turn;
+use crate::Guard;
+use crate::Loop;
+use crate::StepEvent;
+
+/// Every step of a guard's patrol, which can be saved as JSON lines and replayed later.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Trace {
+    pub start: Position,
+    /// Map the guard patrols, with any added obstruction.
+    pub map: Map,
+    pub rules: Rules,
+    /// Event of each step, the first one being step 1.
+    pub events: Vec<StepEvent>,
+}
+
+impl Trace {
+    /// Records the steps of the guard until it leaves the map, walks in a loop or reaches its
+    /// step limit.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the guard has already left the map.
+    pub fn record(guard: &mut Guard) -> Self {
+        let start = guard
+            .position()
+            .expect("the guard must be on the map to record its steps");
+
+        Trace {
+            start,
+            map: guard.map.clone(),
+            rules: guard.rules.clone(),
+            events: guard.steps().collect(),
+        }
+    }
+
+    /// The guard as it was after the given number of steps, walked again from the map and rules
+    /// of the trace. Steps after the end of the trace give the guard at the end.
+    pub fn guard_at(&self, step: usize) -> Guard {
+        let mut guard = Guard::with_rules(self.start, self.map.clone(), self.rules.clone());
+        guard.steps().take(step).for_each(drop);
+
+        guard
+    }
+
+    /// Position of the guard after the given number of steps, or `None` if it has left the map
+    /// by then. Steps after the end of the trace keep the last position.
+    pub fn position_at(&self, step: usize) -> Option<Position> {
+        self.positions()
+            .take(step.saturating_add(1))
+            .last()
+            .flatten()
+    }
+
+    /// Points the guard has stood on up to and including the given step.
+    pub fn visited_points_at(&self, step: usize) -> HashSet<Coordinate> {
+        self.positions()
+            .take(step.saturating_add(1))
+            .flatten()
+            .map(|position| position.point)
+            .collect()
+    }
+
+    /// Position of the guard at the start and after each step.
+    fn positions(&self) -> impl Iterator<Item = Option<Position>> + '_ {
+        let mut position = Some(self.start);
+
+        std::iter::once(position).chain(self.events.iter().map(move |event| {
+            position = match event {
+                StepEvent::Moved(next) | StepEvent::Turned(next) => Some(*next),
+                StepEvent::LeftGrid => None,
+                StepEvent::LoopDetected(cycle) => cycle.positions.first().copied(),
+                StepEvent::StepLimitReached(_) => position,
+            };
+            position
+        }))
+    }
+
+    /// First step at which the two traces differ, with 0 for different starts. Only the walks
+    /// are compared, the traces may come from different maps or rules.
+    pub fn first_difference(&self, other: &Trace) -> Option<usize> {
+        if self.start != other.start {
+            return Some(0);
+        }
+
+        let common = self
+            .events
+            .iter()
+            .zip(&other.events)
+            .position(|(event, other)| event != other);
+
+        match common {
+            Some(index) => Some(index + 1),
+            None if self.events.len() != other.events.len() => {
+                Some(self.events.len().min(other.events.len()) + 1)
+            }
+            None => None,
+        }
+    }
+
+    /// Writes one JSON object per line: the start of the guard with the map and rules, then
+    /// each step.
+    pub fn write_json_lines(&self, mut writer: impl Write) -> io::Result<()> {
+        let start = Record::Start {
+            position: self.start.into(),
+            map: self.map.to_string(),
+            turns: self.rules.turns().iter().map(Turn::to_string).collect(),
+            max_steps: self.rules.max_steps,
+        };
+        let records = std::iter::once(start).chain(
+            self.events
+                .iter()
+                .enumerate()
+                .map(|(index, event)| Record::from_event(index + 1, event)),
+        );
+
+        for record in records {
+            serde_json::to_writer(&mut writer, &record)?;
+            writeln!(writer)?;
+        }
+
+        Ok(())
+    }
+
+    /// Reads a trace written by [`Trace::write_json_lines`].
+    pub fn read_json_lines(reader: impl BufRead) -> Result<Self, TraceError> {
+        let mut header = None;
+        let mut events = Vec::new();
+
+        for (index, line) in reader.lines().enumerate() {
+            let line_number = index + 1;
+            let line = line.map_err(TraceError::Io)?;
+            let record = serde_json::from_str(&line).map_err(|error| TraceError::Json {
+                line: line_number,
+                error,
+            })?;
+            let invalid = |reason| TraceError::InvalidRecord {
+                line: line_number,
+                reason,
+            };
+
+            match (record, &header) {
+                (
+                    Record::Start {
+                        position,
+                        map,
+                        turns,
+                        max_steps,
+                    },
+                    None,
+                ) => {
+                    let start: Position = position.try_into().map_err(invalid)?;
+                    let map =
+                        Map::parse_multiple_guards(&map).map_err(|_| invalid("invalid map"))?;
+                    if !map.is_point_on_grid(start.point) || map.is_point_obstructed(start.point) {
+                        return Err(invalid("start not on the map"));
+                    }
+                    if turns.is_empty() {
+                        return Err(invalid("no turns"));
+                    }
+                    let turns = turns
+                        .iter()
+                        .map(|turn| turn.parse())
+                        .collect::<Result<_, _>>()
+                        .map_err(|_| invalid("invalid turn"))?;
+                    let mut rules = Rules::sequence(turns);
+                    rules.max_steps = max_steps;
+                    header = Some((start, map, rules));
+                }
+                (Record::Start { .. }, Some(_)) => {
+                    return Err(invalid("start after the first line"))
+                }
+                (_, None) => return Err(invalid("step before the start")),
+                (record, Some(_)) => {
+                    if record.step() != Some(events.len() + 1) {
+                        return Err(invalid("step out of order"));
+                    }
+                    events.push(record.into_event().map_err(invalid)?);
+                }
+            }
+        }
+
+        let (start, map, rules) = header.ok_or(TraceError::MissingStart)?;
+
+        Ok(Trace {
+            start,
+            map,
+            rules,
+            events,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub enum TraceError {
+    Io(io::Error),
+    Json {
+        line: usize,
+        error: serde_json::Error,
+    },
+    InvalidRecord {
+        line: usize,
+        reason: &'static str,
+    },
+    MissingStart,
+}
+
+impl Display for TraceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TraceError::Io(error) => write!(f, "Could not read trace: {error}"),
+            TraceError::Json { line, error } => write!(f, "Invalid JSON at line {line}: {error}"),
+            TraceError::InvalidRecord { line, reason } => {
+                write!(f, "Invalid record at line {line}: {reason}")
+            }
+            TraceError::MissingStart => write!(f, "Trace has no start"),
+        }
+    }
+}
+
+impl Error for TraceError {}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+struct PositionRecord {
+    row: usize,
+    col: usize,
+    direction: char,
+}
+
+impl From<Position> for PositionRecord {
+    fn from(position: Position) -> Self {
+        PositionRecord {
+            row: position.point.row,
+            col: position.point.col,
+            direction: position.direction.into(),
+        }
+    }
+}
+
+impl TryFrom<PositionRecord> for Position {
+    type Error = &'static str;
+
+    fn try_from(record: PositionRecord) -> Result<Self, Self::Error> {
+        Ok(Position {
+            point: Coordinate::new(record.row, record.col),
+            direction: Direction::try_from(record.direction).map_err(|_| "invalid direction")?,
+        })
+    }
+}
+
+/// One line of a trace file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+enum Record {
+    Start {
+        position: PositionRecord,
+        /// The map as it is parsed from text.
+        map: String,
+        turns: Vec<String>,
+        max_steps: Option<usize>,
+    },
+    Moved {
+        step: usize,
+        position: PositionRecord,
+    },
+    Turned {
+        step: usize,
+        position: PositionRecord,
+    },
+    LeftGrid {
+        step: usize,
+    },
+    LoopDetected {
+        step: usize,
+        entry_step: usize,
+        positions: Vec<PositionRecord>,
+    },
+    StepLimitReached {
+        step: usize,
+        steps: usize,
+    },
+}
+
+impl Record {
+    fn from_event(step: usize, event: &StepEvent) -> Self {
+        match event {
+            StepEvent::Moved(position) => Record::Moved {
+                step,
+                position: (*position).into(),
+            },
+            StepEvent::Turned(position) => Record::Turned {
+                step,
+                position: (*position).into(),
+            },
+            StepEvent::LeftGrid => Record::LeftGrid { step },
+            StepEvent::LoopDetected(cycle) => Record::LoopDetected {
+                step,
+                entry_step: cycle.entry_step,
+                positions: cycle.positions.iter().map(|&p| p.into()).collect(),
+            },
+            StepEvent::StepLimitReached(steps) => Record::StepLimitReached {
+                step,
+                steps: *steps,
+            },
+        }
+    }
+
+    fn step(&self) -> Option<usize> {
+        match self {
+            Record::Start { .. } => None,
+            Record::Moved { step, .. }
+            | Record::Turned { step, .. }
+            | Record::LeftGrid { step }
+            | Record::LoopDetected { step, .. }
+            | Record::StepLimitReached { step, .. } => Some(*step),
+        }
+    }
+
+    fn into_event(self) -> Result<StepEvent, &'static str> {
+        match self {
+            Record::Start { .. } => Err("start is not a step"),
+            Record::Moved { position, .. } => Ok(StepEvent::Moved(position.try_into()?)),
+            Record::Turned { position, .. } => Ok(StepEvent::Turned(position.try_into()?)),
+            Record::LeftGrid { .. } => Ok(StepEvent::LeftGrid),
+            Record::LoopDetected {
+                entry_step,
+                positions,
+                ..
+            } => Ok(StepEvent::LoopDetected(Loop {
+                entry_step,
+                positions: positions
+                    .into_iter()
+                    .map(Position::try_from)
+                    .collect::<Result<_, _>>()?,
+            })),
+            Record::StepLimitReached { steps, .. } => Ok(StepEvent::StepLimitReached(steps)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::map::Map;
+
+    const SAMPLE: &str = include_str!("../sample.txt");
+
+    fn sample_trace() -> Trace {
+        Trace::record(&mut Guard::new(SAMPLE.parse().unwrap()))
+    }
+
+    #[test]
+    fn test_record() {
+        let trace = sample_trace();
+
+        assert_eq!(trace.start.point, Coordinate::new(6, 4));
+        assert_eq!(trace.events.len(), 55);
+        assert_eq!(trace.events.last(), Some(&StepEvent::LeftGrid));
+    }
+
+    #[test]
+    fn test_json_lines_round_trip() {
+        let trace = sample_trace();
+        let mut json = Vec::new();
+
+        trace.write_json_lines(&mut json).unwrap();
+
+        let json = String::from_utf8(json).unwrap();
+        assert_eq!(json.lines().count(), 56);
+        assert_eq!(
+            json.lines().nth(1).unwrap(),
+            r#"{"event":"moved","step":1,"position":{"row":5,"col":4,"direction":"^"}}"#
+        );
+        assert_eq!(Trace::read_json_lines(json.as_bytes()).unwrap(), trace);
+    }
+
+    #[test]
+    fn test_json_lines_header() {
+        let map: Map = "..#\n...\n..^".parse().unwrap();
+        let rules = Rules::counter_clockwise().with_max_steps(10);
+        let trace = Trace::record(&mut Guard::with_rules(
+            map.guard_start_position(),
+            map,
+            rules,
+        ));
+        let mut json = Vec::new();
+
+        trace.write_json_lines(&mut json).unwrap();
+
+        assert_eq!(
+            String::from_utf8(json).unwrap().lines().next().unwrap(),
+            r#"{"event":"start","position":{"row":2,"col":2,"direction":"^"},"map":"..#\n...\n..^\n","turns":["left"],"max_steps":10}"#
+        );
+    }
+
+    #[test]
+    fn test_json_lines_round_trip_with_loop() {
+        let mut map: Map = SAMPLE.parse().unwrap();
+        map.add_obstruction(Coordinate::new(6, 3));
+        let trace = Trace::record(&mut Guard::new(map));
+        let mut json = Vec::new();
+
+        trace.write_json_lines(&mut json).unwrap();
+
+        assert!(matches!(
+            trace.events.last(),
+            Some(StepEvent::LoopDetected(_))
+        ));
+        assert_eq!(Trace::read_json_lines(&json[..]).unwrap(), trace);
+    }
+
+    #[test]
+    fn test_replay_state_at_step() {
+        let trace = sample_trace();
+        let mut guard = Guard::new(SAMPLE.parse().unwrap());
+
+        for step in 0..trace.events.len() {
+            assert_eq!(trace.position_at(step), guard.position(), "at step {step}");
+            assert_eq!(trace.guard_at(step).position(), guard.position());
+            guard.r#move();
+        }
+        assert_eq!(trace.position_at(trace.events.len()), None);
+        assert_eq!(trace.position_at(usize::MAX), None);
+        assert_eq!(trace.guard_at(usize::MAX).position(), None);
+        assert_eq!(trace.visited_points_at(1000).len(), 41);
+        assert_eq!(trace.visited_points_at(0).len(), 1);
+    }
+
+    #[test]
+    fn test_first_difference() {
+        let trace = sample_trace();
+        let mut map: Map = SAMPLE.parse().unwrap();
+        map.add_obstruction(Coordinate::new(3, 4));
+        let other = Trace::record(&mut Guard::new(map));
+
+        assert_eq!(trace.first_difference(&trace), None);
+        // the guard turns in front of the new obstruction instead of moving on
+        assert_eq!(trace.first_difference(&other), Some(3));
+    }
+
+    #[test]
+    fn test_read_invalid_trace() {
+        assert!(matches!(
+            Trace::read_json_lines("".as_bytes()),
+            Err(TraceError::MissingStart)
+        ));
+        assert!(matches!(
+            Trace::read_json_lines("{\"event\":\"left_grid\",\"step\":1}".as_bytes()),
+            Err(TraceError::InvalidRecord { line: 1, .. })
+        ));
+        let start = |direction, map, turns| {
+            format!(
+                r#"{{"event":"start","position":{{"row":0,"col":0,"direction":"{direction}"}},"map":"{map}","turns":[{turns}],"max_steps":null}}"#
+            )
+        };
+        assert!(matches!(
+            Trace::read_json_lines(
+                format!("{}\nnot json", start("^", "^.", r#""right""#)).as_bytes()
+            ),
+            Err(TraceError::Json { line: 2, .. })
+        ));
+        assert!(matches!(
+            Trace::read_json_lines(start("x", "^.", r#""right""#).as_bytes()),
+            Err(TraceError::InvalidRecord { line: 1, .. })
+        ));
+        assert!(matches!(
+            Trace::read_json_lines(start("^", "^#x", r#""right""#).as_bytes()),
+            Err(TraceError::InvalidRecord {
+                line: 1,
+                reason: "invalid map"
+            })
+        ));
+        assert!(matches!(
+            Trace::read_json_lines(
+                r#"{"event":"start","position":{"row":99,"col":99,"direction":"^"},"map":"^..","turns":["right"],"max_steps":null}"#
+                    .as_bytes()
+            ),
+            Err(TraceError::InvalidRecord {
+                line: 1,
+                reason: "start not on the map"
+            })
+        ));
+        assert!(matches!(
+            Trace::read_json_lines(start("^", "#^", r#""right""#).as_bytes()),
+            Err(TraceError::InvalidRecord {
+                line: 1,
+                reason: "start not on the map"
+            })
+        ));
+        assert!(matches!(
+            Trace::read_json_lines(start("^", "^.", "").as_bytes()),
+            Err(TraceError::InvalidRecord {
+                line: 1,
+                reason: "no turns"
+            })
+        ));
+        assert!(matches!(
+            Trace::read_json_lines(
+                "{\"event\":\"start\",\"position\":{\"row\":0,\"col\":0,\"direction\":\"^\"}}"
+                    .as_bytes()
+            ),
+            Err(TraceError::Json { line: 1, .. })
+        ));
+    }
+}
@@ -0,0 +1,71 @@
+use aoc_common::DynSolution;
+
+/// Every solution in the workspace, ordered by year and day.
+pub const SOLUTIONS: &[&dyn DynSolution] = &[
+    &aoc1801::Day01,
+    &aoc1802::Day02,
+    &aoc2001::Day01,
+    &aoc2002::Day02,
+    &aoc2401::Day01,
+    &aoc2402::Day02,
+    &aoc2403::Day03,
+    &aoc2404::Day04,
+    &aoc2405::Day05,
+    &aoc2406::Day06,
+];
+
+/// Finds the solution of the puzzle of the given year and day.
+pub fn find(year: u16, day: u8) -> Option<&'static dyn DynSolution> {
+    SOLUTIONS
+        .iter()
+        .find(|solution| solution.year() == year && solution.day() == day)
+        .copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::Answer;
+    use aoc_common::Part;
+
+    use super::*;
+
+    #[test]
+    fn test_solutions_are_ordered_and_unique() {
+        let keys = SOLUTIONS
+            .iter()
+            .map(|solution| (solution.year(), solution.day()))
+            .collect::<Vec<_>>();
+
+        assert!(keys.windows(2).all(|pair| pair[0] < pair[1]), "{keys:?}");
+    }
+
+    #[test]
+    fn test_find_solution() {
+        let solution = find(2024, 6).unwrap();
+
+        assert_eq!((solution.year(), solution.day()), (2024, 6));
+        assert!(find(2018, 3).is_none());
+        assert!(find(2019, 1).is_none());
+    }
+
+    #[test]
+    fn test_solve_through_registry() {
+        let solution = find(2020, 1).unwrap();
+        let input = "1721\n979\n366\n299\n675\n1456\n";
+
+        let answers: Vec<_> = solution
+            .solve(input, None)
+            .unwrap()
+            .into_iter()
+            .map(|(part, answer)| (part, answer.unwrap()))
+            .collect();
+
+        assert_eq!(
+            answers,
+            vec![
+                (Part::One, Answer::from(514579)),
+                (Part::Two, Answer::from(241861950)),
+            ]
+        );
+    }
+}
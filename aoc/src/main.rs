@@ -0,0 +1,77 @@
+use std::error::Error;
+use std::fs;
+use std::io::Read;
+use std::path::Path;
+use std::path::PathBuf;
+
+use aoc_common::Part;
+use structopt::StructOpt;
+
+/// Runs the solution of an Advent of Code puzzle.
+///
+/// The puzzle input is read from `<year>/<day>/input.txt` in the workspace, unless another
+/// input file is given.
+#[derive(Debug, StructOpt)]
+#[structopt(name = "aoc")]
+struct Opt {
+    /// Year of the puzzle
+    year: u16,
+
+    /// Day of the puzzle
+    day: u8,
+
+    /// Only solve the given part of the puzzle (1 or 2)
+    #[structopt(short, long)]
+    part: Option<Part>,
+
+    /// File to read the puzzle input from, or "-" to read it from stdin
+    #[structopt(short, long, parse(from_os_str))]
+    input: Option<PathBuf>,
+}
+
+fn default_input_path(year: u16, day: u8) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join(year.to_string())
+        .join(format!("{day:02}"))
+        .join("input.txt")
+}
+
+fn read_input(path: &Path) -> Result<String, Box<dyn Error>> {
+    if path == Path::new("-") {
+        let mut input = String::new();
+        std::io::stdin().read_to_string(&mut input)?;
+        Ok(input)
+    } else {
+        fs::read_to_string(path)
+            .map_err(|e| format!("could not read input file {}: {e}", path.display()).into())
+    }
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let opt = Opt::from_args();
+
+    let solution = aoc::find(opt.year, opt.day)
+        .ok_or_else(|| format!("no solution for {} day {}", opt.year, opt.day))?;
+
+    let input_path = opt
+        .input
+        .unwrap_or_else(|| default_input_path(opt.year, opt.day));
+    let input = read_input(&input_path)?;
+
+    println!("Advent of Code {} - Day {}", opt.year, opt.day);
+
+    aoc_common::print_answers(solution.solve(&input, opt.part)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_input_path() {
+        let path = default_input_path(2024, 6);
+
+        assert!(path.ends_with("2024/06/input.txt"));
+    }
+}
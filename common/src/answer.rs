@@ -0,0 +1,71 @@
+use std::fmt::Display;
+
+/// The answer to one part of a puzzle.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Integer(i128),
+    Text(String),
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Integer(integer) => write!(f, "{integer}"),
+            Answer::Text(text) => write!(f, "{text}"),
+        }
+    }
+}
+
+macro_rules! impl_from_integer {
+    ($($integer:ty),*) => {
+        $(
+            impl From<$integer> for Answer {
+                fn from(integer: $integer) -> Self {
+                    Answer::Integer(integer as i128)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_integer!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, usize);
+
+impl From<String> for Answer {
+    fn from(text: String) -> Self {
+        Answer::Text(text)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(text: &str) -> Self {
+        Answer::Text(text.to_owned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_answer_from_integers() {
+        assert_eq!(Answer::from(-3i32), Answer::Integer(-3));
+        assert_eq!(Answer::from(514579u32), Answer::Integer(514579));
+        assert_eq!(Answer::from(41usize), Answer::Integer(41));
+        assert_eq!(Answer::from(u64::MAX), Answer::Integer(u64::MAX as i128));
+    }
+
+    #[test]
+    fn test_answer_from_text() {
+        assert_eq!(Answer::from("fgij"), Answer::Text("fgij".to_owned()));
+        assert_eq!(
+            Answer::from("fgij".to_owned()),
+            Answer::Text("fgij".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_answer_display() {
+        assert_eq!(Answer::from(-3).to_string(), "-3");
+        assert_eq!(Answer::from("fgij").to_string(), "fgij");
+    }
+}
@@ -0,0 +1,10 @@
+mod answer;
+mod solution;
+
+pub use self::answer::Answer;
+pub use self::solution::print_answers;
+pub use self::solution::solve_stdin;
+pub use self::solution::Answers;
+pub use self::solution::DynSolution;
+pub use self::solution::Part;
+pub use self::solution::Solution;
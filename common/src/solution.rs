@@ -0,0 +1,217 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::io::Read;
+use std::str::FromStr;
+
+use crate::Answer;
+
+/// One of the two parts of a puzzle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Part::One => write!(f, "1"),
+            Part::Two => write!(f, "2"),
+        }
+    }
+}
+
+impl FromStr for Part {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            _ => Err("part must be either 1 or 2"),
+        }
+    }
+}
+
+/// The solution of a puzzle.
+///
+/// The input is parsed once and then shared by both parts of the puzzle.
+pub trait Solution {
+    const YEAR: u16;
+    const DAY: u8;
+
+    type Input;
+
+    fn parse(input: &str) -> Result<Self::Input, Box<dyn Error>>;
+
+    fn part1(input: &Self::Input) -> Result<Answer, Box<dyn Error>>;
+
+    fn part2(input: &Self::Input) -> Result<Answer, Box<dyn Error>>;
+}
+
+/// Answer of each part solved, or why it could not be solved.
+pub type Answers = Vec<(Part, Result<Answer, Box<dyn Error>>)>;
+
+/// Object-safe version of [`Solution`], so solutions of different puzzles can be kept together.
+pub trait DynSolution {
+    fn year(&self) -> u16;
+
+    fn day(&self) -> u8;
+
+    /// Parses the input and solves the given part, or both parts if none is given.
+    ///
+    /// Only fails if the input cannot be parsed: a part that fails does not hide the answer of
+    /// the other one.
+    fn solve(&self, input: &str, part: Option<Part>) -> Result<Answers, Box<dyn Error>>;
+}
+
+impl<S: Solution> DynSolution for S {
+    fn year(&self) -> u16 {
+        S::YEAR
+    }
+
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn solve(&self, input: &str, part: Option<Part>) -> Result<Answers, Box<dyn Error>> {
+        Ok(solve_parts::<S>(&S::parse(input)?, part))
+    }
+}
+
+fn solve_parts<S: Solution>(input: &S::Input, part: Option<Part>) -> Answers {
+    Part::ALL
+        .into_iter()
+        .filter(|&p| part.is_none_or(|part| part == p))
+        .map(|p| match p {
+            Part::One => (p, S::part1(input)),
+            Part::Two => (p, S::part2(input)),
+        })
+        .collect()
+}
+
+/// Prints the answer of each part, or why it could not be solved, failing if any part did.
+pub fn print_answers(answers: Answers) -> Result<(), Box<dyn Error>> {
+    let mut failed = Vec::new();
+
+    for (part, answer) in answers {
+        match answer {
+            Ok(answer) => println!("Part {part}: {answer}"),
+            Err(error) => {
+                println!("Part {part} failed: {error}");
+                failed.push(part.to_string());
+            }
+        }
+    }
+
+    if failed.is_empty() {
+        Ok(())
+    } else {
+        Err(format!("could not solve part {}", failed.join(" and ")).into())
+    }
+}
+
+/// Reads the input from stdin, solves both parts of the puzzle and prints their answers.
+pub fn solve_stdin<S: Solution>() -> Result<(), Box<dyn Error>> {
+    let mut input = String::new();
+    std::io::stdin().read_to_string(&mut input)?;
+
+    let input = S::parse(&input)?;
+
+    print_answers(solve_parts::<S>(&input, None))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Lines;
+
+    impl Solution for Lines {
+        const YEAR: u16 = 2000;
+        const DAY: u8 = 1;
+
+        type Input = Vec<String>;
+
+        fn parse(input: &str) -> Result<Self::Input, Box<dyn Error>> {
+            Ok(input.lines().map(str::to_owned).collect())
+        }
+
+        fn part1(input: &Self::Input) -> Result<Answer, Box<dyn Error>> {
+            Ok(input.len().into())
+        }
+
+        fn part2(input: &Self::Input) -> Result<Answer, Box<dyn Error>> {
+            input
+                .last()
+                .map(|line| line.as_str().into())
+                .ok_or("no lines".into())
+        }
+    }
+
+    #[test]
+    fn test_part_from_str() {
+        assert_eq!("1".parse::<Part>(), Ok(Part::One));
+        assert_eq!("2".parse::<Part>(), Ok(Part::Two));
+        assert!("3".parse::<Part>().is_err());
+    }
+
+    #[test]
+    fn test_dyn_solution_key() {
+        let solution: &dyn DynSolution = &Lines;
+
+        assert_eq!((solution.year(), solution.day()), (2000, 1));
+    }
+
+    /// Answers of the parts, `None` for those that failed.
+    fn solved(answers: Answers) -> Vec<(Part, Option<Answer>)> {
+        answers
+            .into_iter()
+            .map(|(part, answer)| (part, answer.ok()))
+            .collect()
+    }
+
+    #[test]
+    fn test_dyn_solution_solve_both_parts() {
+        let answers = Lines.solve("a\nb\nc", None).unwrap();
+
+        assert_eq!(
+            solved(answers),
+            vec![
+                (Part::One, Some(Answer::from(3))),
+                (Part::Two, Some(Answer::from("c")))
+            ]
+        );
+    }
+
+    #[test]
+    fn test_dyn_solution_solve_one_part() {
+        let answers = Lines.solve("a\nb\nc", Some(Part::Two)).unwrap();
+
+        assert_eq!(solved(answers), vec![(Part::Two, Some(Answer::from("c")))]);
+    }
+
+    #[test]
+    fn test_dyn_solution_solve_error() {
+        // part 2 fails without lines, but part 1 is still answered
+        assert_eq!(
+            solved(Lines.solve("", None).unwrap()),
+            vec![(Part::One, Some(Answer::from(0))), (Part::Two, None)]
+        );
+    }
+
+    #[test]
+    fn test_print_answers() {
+        assert!(print_answers(Lines.solve("a", None).unwrap()).is_ok());
+        assert_eq!(
+            print_answers(Lines.solve("", None).unwrap())
+                .unwrap_err()
+                .to_string(),
+            "could not solve part 2"
+        );
+    }
+}
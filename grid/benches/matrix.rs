@@ -0,0 +1,105 @@
+//! Compares the flat storage of `Matrix` against the previous `Vec<Vec<T>>` storage, counting
+//! "XMAS" words (2024, day 4) on the day's sample tiled into a larger grid.
+
+use std::hint::black_box;
+
+use aoc_grid::Coordinate;
+use aoc_grid::Direction;
+use aoc_grid::Matrix;
+use criterion::criterion_group;
+use criterion::criterion_main;
+use criterion::Criterion;
+
+const SAMPLE: &str = include_str!("../../2024/04/sample.txt");
+
+/// How many times the sample is repeated horizontally and vertically.
+const TILES: usize = 50;
+
+trait CharGrid {
+    fn get(&self, coordinate: Coordinate) -> Option<&char>;
+
+    fn iter_enumerate(&self) -> impl Iterator<Item = (Coordinate, &char)>;
+}
+
+impl CharGrid for Matrix<char> {
+    fn get(&self, coordinate: Coordinate) -> Option<&char> {
+        Matrix::get(self, coordinate)
+    }
+
+    fn iter_enumerate(&self) -> impl Iterator<Item = (Coordinate, &char)> {
+        Matrix::iter_enumerate(self)
+    }
+}
+
+/// The storage `Matrix` used before being flattened.
+struct NestedMatrix {
+    matrix: Vec<Vec<char>>,
+}
+
+impl CharGrid for NestedMatrix {
+    fn get(&self, coordinate: Coordinate) -> Option<&char> {
+        self.matrix
+            .get(coordinate.row)
+            .and_then(|row| row.get(coordinate.col))
+    }
+
+    fn iter_enumerate(&self) -> impl Iterator<Item = (Coordinate, &char)> {
+        self.matrix.iter().enumerate().flat_map(|(row_idx, row)| {
+            row.iter()
+                .enumerate()
+                .map(move |(col_idx, elem)| (Coordinate::new(row_idx, col_idx), elem))
+        })
+    }
+}
+
+fn count_xmas(grid: &impl CharGrid) -> usize {
+    grid.iter_enumerate()
+        .filter(|(_, &elem)| elem == 'X')
+        .map(|(from_position, _)| {
+            Direction::ALL
+                .into_iter()
+                .filter(|&direction| {
+                    let mut position = from_position;
+                    "MAS".chars().all(|letter| {
+                        position
+                            .checked_add(direction)
+                            .inspect(|&next| position = next)
+                            .and_then(|next| grid.get(next))
+                            .is_some_and(|&elem| elem == letter)
+                    })
+                })
+                .count()
+        })
+        .sum()
+}
+
+fn scaled_sample() -> Vec<Vec<char>> {
+    let lines = SAMPLE
+        .lines()
+        .map(|line| line.repeat(TILES).chars().collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+
+    lines
+        .iter()
+        .cycle()
+        .take(lines.len() * TILES)
+        .cloned()
+        .collect()
+}
+
+fn bench_count_xmas(c: &mut Criterion) {
+    let flat = Matrix::try_from(scaled_sample()).unwrap();
+    let nested = NestedMatrix {
+        matrix: scaled_sample(),
+    };
+
+    assert_eq!(count_xmas(&flat), count_xmas(&nested));
+
+    let mut group = c.benchmark_group("count_xmas");
+    group.bench_function("flat", |b| b.iter(|| count_xmas(black_box(&flat))));
+    group.bench_function("nested", |b| b.iter(|| count_xmas(black_box(&nested))));
+    group.finish();
+}
+
+criterion_group!(benches, bench_count_xmas);
+criterion_main!(benches);
@@ -0,0 +1,171 @@
+use std::fmt::Display;
+use std::ops::Add;
+
+use crate::Direction;
+
+#[derive(Debug, Default, Clone, Copy, Hash, Eq, PartialEq, PartialOrd, Ord)]
+pub struct Coordinate {
+    pub row: usize,
+    pub col: usize,
+}
+
+impl Coordinate {
+    pub fn new(row: usize, col: usize) -> Self {
+        Coordinate { row, col }
+    }
+
+    /// Moves the coordinate by the given distance, returning `None` if a row or column would
+    /// become negative or overflow.
+    pub fn checked_add(&self, distance: impl Into<Distance>) -> Option<Coordinate> {
+        let distance = distance.into();
+        Some(Coordinate::new(
+            self.row.checked_add_signed(distance.row_delta)?,
+            self.col.checked_add_signed(distance.col_delta)?,
+        ))
+    }
+
+    /// Neighbour of the coordinate towards the given direction, if it is not out of range.
+    pub fn next_towards(&self, direction: Direction) -> Option<Coordinate> {
+        self.checked_add(direction)
+    }
+}
+
+impl Display for Coordinate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.row, self.col)
+    }
+}
+
+impl From<(usize, usize)> for Coordinate {
+    fn from((row, col): (usize, usize)) -> Self {
+        Coordinate::new(row, col)
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, Hash, Eq, PartialEq)]
+pub struct Distance {
+    pub row_delta: isize,
+    pub col_delta: isize,
+}
+
+impl Distance {
+    pub fn new(row_delta: isize, col_delta: isize) -> Self {
+        Distance {
+            row_delta,
+            col_delta,
+        }
+    }
+}
+
+impl From<(isize, isize)> for Distance {
+    fn from((row_delta, col_delta): (isize, isize)) -> Self {
+        Distance::new(row_delta, col_delta)
+    }
+}
+
+/// Panics if the resulting coordinate is out of range; see [`Coordinate::checked_add`] for a
+/// non-panicking version.
+impl Add<Distance> for Coordinate {
+    type Output = Coordinate;
+
+    fn add(self, rhs: Distance) -> Self::Output {
+        self.checked_add(rhs)
+            .expect("coordinate out of range when adding distance")
+    }
+}
+
+impl Add<(isize, isize)> for Coordinate {
+    type Output = Coordinate;
+
+    fn add(self, rhs: (isize, isize)) -> Self::Output {
+        self + Distance::from(rhs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_coordinate_from_tuple() {
+        let coordinate = Coordinate::from((1, 2));
+        assert_eq!(1, coordinate.row);
+        assert_eq!(2, coordinate.col);
+    }
+
+    #[test]
+    fn test_distance_from_tuple() {
+        let distance = Distance::from((1, 2));
+        assert_eq!(1, distance.row_delta);
+        assert_eq!(2, distance.col_delta);
+    }
+
+    #[test]
+    fn test_coordinate_add_distance() {
+        let coordinate = Coordinate::from((1, 2));
+        let distance = Distance::from((3, 4));
+        assert_eq!(Coordinate::from((4, 6)), coordinate + distance);
+    }
+
+    #[test]
+    fn test_coordinate_add_tuple() {
+        let coordinate = Coordinate::from((1, 2));
+        assert_eq!(Coordinate::from((2, 3)), coordinate + (1, 1));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_coordinate_add_out_of_range() {
+        let _ = Coordinate::new(0, 2) + (-1, 0);
+    }
+
+    #[test]
+    fn test_coordinate_checked_add() {
+        let coordinate = Coordinate::new(1, 2);
+        assert_eq!(
+            coordinate.checked_add((-1, -2)),
+            Some(Coordinate::new(0, 0))
+        );
+        assert_eq!(coordinate.checked_add((-2, 0)), None);
+        assert_eq!(coordinate.checked_add(Distance::new(0, -3)), None);
+        assert_eq!(Coordinate::new(usize::MAX, 0).checked_add((1, 0)), None);
+    }
+
+    #[test]
+    fn test_coordinate_next_towards() {
+        let coordinate = Coordinate::new(1, 1);
+        assert_eq!(
+            coordinate.next_towards(Direction::North),
+            Some(Coordinate::new(0, 1))
+        );
+        assert_eq!(
+            coordinate.next_towards(Direction::South),
+            Some(Coordinate::new(2, 1))
+        );
+        assert_eq!(
+            coordinate.next_towards(Direction::East),
+            Some(Coordinate::new(1, 2))
+        );
+        assert_eq!(
+            coordinate.next_towards(Direction::West),
+            Some(Coordinate::new(1, 0))
+        );
+        assert_eq!(
+            coordinate.next_towards(Direction::SouthEast),
+            Some(Coordinate::new(2, 2))
+        );
+    }
+
+    #[test]
+    fn test_coordinate_next_towards_out_of_range() {
+        let coordinate = Coordinate::new(0, 0);
+        assert_eq!(coordinate.next_towards(Direction::North), None);
+        assert_eq!(coordinate.next_towards(Direction::West), None);
+        assert_eq!(coordinate.next_towards(Direction::NorthEast), None);
+    }
+
+    #[test]
+    fn test_coordinate_display() {
+        assert_eq!(Coordinate::new(6, 4).to_string(), "(6, 4)");
+    }
+}
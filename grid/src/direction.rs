@@ -0,0 +1,206 @@
+use std::fmt::Display;
+
+use crate::Distance;
+use crate::GridError;
+
+#[derive(Debug, Default, Clone, Copy, Hash, Eq, PartialEq)]
+pub enum Direction {
+    #[default]
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Direction {
+    /// All directions, clockwise from north.
+    pub const ALL: [Direction; 8] = [
+        Direction::North,
+        Direction::NorthEast,
+        Direction::East,
+        Direction::SouthEast,
+        Direction::South,
+        Direction::SouthWest,
+        Direction::West,
+        Direction::NorthWest,
+    ];
+
+    /// Directions along rows and columns, clockwise from north.
+    pub const ORTHOGONAL: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
+    /// Diagonal directions, clockwise from north-east.
+    pub const DIAGONAL: [Direction; 4] = [
+        Direction::NorthEast,
+        Direction::SouthEast,
+        Direction::SouthWest,
+        Direction::NorthWest,
+    ];
+
+    pub fn distance(&self) -> Distance {
+        match self {
+            Direction::North => Distance::new(-1, 0),
+            Direction::NorthEast => Distance::new(-1, 1),
+            Direction::East => Distance::new(0, 1),
+            Direction::SouthEast => Distance::new(1, 1),
+            Direction::South => Distance::new(1, 0),
+            Direction::SouthWest => Distance::new(1, -1),
+            Direction::West => Distance::new(0, -1),
+            Direction::NorthWest => Distance::new(-1, -1),
+        }
+    }
+
+    /// Rotates the direction by 90 degrees clockwise.
+    pub fn rotate_clockwise(&self) -> Direction {
+        self.rotate_eighths(2)
+    }
+
+    /// Rotates the direction by 90 degrees counter-clockwise.
+    pub fn rotate_counter_clockwise(&self) -> Direction {
+        self.rotate_eighths(6)
+    }
+
+    pub fn opposite(&self) -> Direction {
+        self.rotate_eighths(4)
+    }
+
+    fn rotate_eighths(&self, eighths: usize) -> Direction {
+        let index = Direction::ALL
+            .iter()
+            .position(|direction| direction == self)
+            .unwrap();
+        Direction::ALL[(index + eighths) % Direction::ALL.len()]
+    }
+}
+
+impl From<Direction> for Distance {
+    fn from(direction: Direction) -> Self {
+        direction.distance()
+    }
+}
+
+impl Display for Direction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", char::from(*self))
+    }
+}
+
+/// Arrow pointing towards the direction, the reverse of `TryFrom<char>`.
+impl From<Direction> for char {
+    fn from(direction: Direction) -> Self {
+        match direction {
+            Direction::North => '^',
+            Direction::NorthEast => '↗',
+            Direction::East => '>',
+            Direction::SouthEast => '↘',
+            Direction::South => 'v',
+            Direction::SouthWest => '↙',
+            Direction::West => '<',
+            Direction::NorthWest => '↖',
+        }
+    }
+}
+
+impl TryFrom<char> for Direction {
+    type Error = GridError;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            '^' => Ok(Direction::North),
+            '↗' => Ok(Direction::NorthEast),
+            '>' => Ok(Direction::East),
+            '↘' => Ok(Direction::SouthEast),
+            'v' => Ok(Direction::South),
+            '↙' => Ok(Direction::SouthWest),
+            '<' => Ok(Direction::West),
+            '↖' => Ok(Direction::NorthWest),
+            _ => Err(GridError::InvalidDirection(c)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_direction_distance() {
+        assert_eq!(Direction::North.distance(), Distance::new(-1, 0));
+        assert_eq!(Direction::South.distance(), Distance::new(1, 0));
+        assert_eq!(Direction::East.distance(), Distance::new(0, 1));
+        assert_eq!(Direction::West.distance(), Distance::new(0, -1));
+        assert_eq!(Direction::NorthWest.distance(), Distance::new(-1, -1));
+    }
+
+    #[test]
+    fn test_direction_rotate_clockwise() {
+        assert_eq!(Direction::North.rotate_clockwise(), Direction::East);
+        assert_eq!(Direction::East.rotate_clockwise(), Direction::South);
+        assert_eq!(Direction::South.rotate_clockwise(), Direction::West);
+        assert_eq!(Direction::West.rotate_clockwise(), Direction::North);
+        assert_eq!(
+            Direction::NorthWest.rotate_clockwise(),
+            Direction::NorthEast
+        );
+    }
+
+    #[test]
+    fn test_direction_rotate_counter_clockwise() {
+        for direction in Direction::ALL {
+            assert_eq!(
+                direction.rotate_clockwise().rotate_counter_clockwise(),
+                direction
+            );
+        }
+    }
+
+    #[test]
+    fn test_direction_opposite() {
+        for direction in Direction::ALL {
+            let distance = direction.distance();
+            let opposite = direction.opposite().distance();
+            assert_eq!(distance.row_delta, -opposite.row_delta);
+            assert_eq!(distance.col_delta, -opposite.col_delta);
+        }
+    }
+
+    #[test]
+    fn test_char_to_direction() {
+        assert_eq!(Direction::try_from('^').unwrap(), Direction::North);
+        assert_eq!(Direction::try_from('v').unwrap(), Direction::South);
+        assert_eq!(Direction::try_from('>').unwrap(), Direction::East);
+        assert_eq!(Direction::try_from('<').unwrap(), Direction::West);
+    }
+
+    #[test]
+    fn test_invalid_char_to_direction() {
+        assert_eq!(
+            Direction::try_from('#'),
+            Err(GridError::InvalidDirection('#'))
+        );
+        assert!(Direction::try_from('.').is_err());
+    }
+
+    #[test]
+    fn test_direction_display_round_trip() {
+        for direction in Direction::ALL {
+            let c = direction.to_string().chars().next().unwrap();
+            assert_eq!(Direction::try_from(c), Ok(direction));
+        }
+    }
+
+    #[test]
+    fn test_direction_char_round_trip() {
+        for direction in Direction::ALL {
+            assert_eq!(Direction::try_from(char::from(direction)), Ok(direction));
+        }
+    }
+}
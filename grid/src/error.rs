@@ -0,0 +1,21 @@
+use std::error::Error;
+use std::fmt::Display;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum GridError {
+    UnequalRowsLength,
+    EmptyGrid,
+    InvalidDirection(char),
+}
+
+impl Error for GridError {}
+
+impl Display for GridError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GridError::UnequalRowsLength => write!(f, "Length of rows are not equal"),
+            GridError::EmptyGrid => write!(f, "Grid is empty"),
+            GridError::InvalidDirection(c) => write!(f, "Invalid direction: {c:?}"),
+        }
+    }
+}
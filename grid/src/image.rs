@@ -0,0 +1,260 @@
+use std::fmt::Display;
+use std::fs::File;
+use std::io;
+use std::io::BufWriter;
+use std::io::Write;
+use std::path::Path;
+
+use crate::Coordinate;
+use crate::Matrix;
+
+/// A colour, with 8 bits per channel.
+#[derive(Debug, Default, Clone, Copy, Hash, Eq, PartialEq)]
+pub struct Colour {
+    pub red: u8,
+    pub green: u8,
+    pub blue: u8,
+}
+
+impl Colour {
+    pub const BLACK: Colour = Colour::new(0, 0, 0);
+    pub const WHITE: Colour = Colour::new(255, 255, 255);
+    pub const GREY: Colour = Colour::new(128, 128, 128);
+    pub const RED: Colour = Colour::new(220, 40, 40);
+    pub const GREEN: Colour = Colour::new(40, 180, 40);
+    pub const BLUE: Colour = Colour::new(60, 110, 220);
+    pub const YELLOW: Colour = Colour::new(240, 200, 40);
+
+    pub const fn new(red: u8, green: u8, blue: u8) -> Self {
+        Colour { red, green, blue }
+    }
+}
+
+/// Hexadecimal notation, as used by CSS and SVG.
+impl Display for Colour {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "#{:02x}{:02x}{:02x}", self.red, self.green, self.blue)
+    }
+}
+
+/// File formats an [`Image`] can be written to.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum ImageFormat {
+    Ppm,
+    Png,
+    Svg,
+}
+
+impl ImageFormat {
+    /// Format matching the extension of the path, if it is a known one.
+    pub fn from_path(path: impl AsRef<Path>) -> Option<Self> {
+        let extension = path.as_ref().extension()?.to_str()?;
+
+        match extension.to_ascii_lowercase().as_str() {
+            "ppm" => Some(ImageFormat::Ppm),
+            "png" => Some(ImageFormat::Png),
+            "svg" => Some(ImageFormat::Svg),
+            _ => None,
+        }
+    }
+}
+
+/// A grid drawn as an image, each cell being a square of a single colour.
+#[derive(Debug, Clone)]
+pub struct Image {
+    cells: Matrix<Colour>,
+    cell_size: usize,
+}
+
+impl Image {
+    /// Size in pixels of the side of a cell, unless changed with [`Image::with_cell_size`].
+    pub const DEFAULT_CELL_SIZE: usize = 8;
+
+    pub fn new(cells: Matrix<Colour>) -> Self {
+        Image {
+            cells,
+            cell_size: Self::DEFAULT_CELL_SIZE,
+        }
+    }
+
+    /// Changes the size in pixels of the side of a cell.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the size is zero.
+    pub fn with_cell_size(mut self, cell_size: usize) -> Self {
+        assert!(cell_size > 0, "cells must be at least one pixel wide");
+        self.cell_size = cell_size;
+        self
+    }
+
+    /// Colour of each cell.
+    pub fn cells(&self) -> &Matrix<Colour> {
+        &self.cells
+    }
+
+    /// Width of the image in pixels.
+    pub fn width(&self) -> usize {
+        self.cells.width() * self.cell_size
+    }
+
+    /// Height of the image in pixels.
+    pub fn height(&self) -> usize {
+        self.cells.height() * self.cell_size
+    }
+
+    /// Red, green and blue values of every pixel, row after row.
+    fn pixels(&self) -> Vec<u8> {
+        self.cells
+            .rows()
+            .flat_map(|row| {
+                let line = row
+                    .iter()
+                    .flat_map(|colour| {
+                        [colour.red, colour.green, colour.blue]
+                            .repeat(self.cell_size)
+                            .into_iter()
+                    })
+                    .collect::<Vec<_>>();
+                line.repeat(self.cell_size)
+            })
+            .collect()
+    }
+
+    /// Writes the image as a binary PPM (netpbm) file.
+    pub fn write_ppm(&self, mut writer: impl Write) -> io::Result<()> {
+        write!(writer, "P6\n{} {}\n255\n", self.width(), self.height())?;
+        writer.write_all(&self.pixels())
+    }
+
+    pub fn write_png(&self, writer: impl Write) -> io::Result<()> {
+        let (width, height) = (self.width(), self.height());
+        let too_large = |_| io::Error::new(io::ErrorKind::InvalidInput, "image too large for PNG");
+
+        let mut encoder = png::Encoder::new(
+            writer,
+            u32::try_from(width).map_err(too_large)?,
+            u32::try_from(height).map_err(too_large)?,
+        );
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&self.pixels())?;
+        writer.finish()?;
+
+        Ok(())
+    }
+
+    /// Writes the image as an SVG file, each cell being a square of `cell_size` units.
+    pub fn write_svg(&self, mut writer: impl Write) -> io::Result<()> {
+        writeln!(
+            writer,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{0}" height="{1}" viewBox="0 0 {0} {1}" shape-rendering="crispEdges">"#,
+            self.width(),
+            self.height()
+        )?;
+        for (Coordinate { row, col }, colour) in self.cells.iter_enumerate() {
+            writeln!(
+                writer,
+                r#"<rect x="{}" y="{}" width="{size}" height="{size}" fill="{colour}"/>"#,
+                col * self.cell_size,
+                row * self.cell_size,
+                size = self.cell_size,
+            )?;
+        }
+        writeln!(writer, "</svg>")
+    }
+
+    pub fn write(&self, format: ImageFormat, writer: impl Write) -> io::Result<()> {
+        match format {
+            ImageFormat::Ppm => self.write_ppm(writer),
+            ImageFormat::Png => self.write_png(writer),
+            ImageFormat::Svg => self.write_svg(writer),
+        }
+    }
+
+    /// Writes the image to a file, in the format given by its extension.
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let path = path.as_ref();
+        let format = ImageFormat::from_path(path).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "unknown image format for {}, expected .ppm, .png or .svg",
+                    path.display()
+                ),
+            )
+        })?;
+
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.write(format, &mut writer)?;
+        writer.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn image() -> Image {
+        let cells = Matrix::try_from(vec![
+            vec![Colour::BLACK, Colour::WHITE, Colour::RED],
+            vec![Colour::GREEN, Colour::BLUE, Colour::YELLOW],
+        ])
+        .unwrap();
+
+        Image::new(cells).with_cell_size(2)
+    }
+
+    #[test]
+    fn test_colour_display() {
+        assert_eq!(Colour::new(255, 16, 0).to_string(), "#ff1000");
+    }
+
+    #[test]
+    fn test_format_from_path() {
+        assert_eq!(ImageFormat::from_path("a/map.PNG"), Some(ImageFormat::Png));
+        assert_eq!(ImageFormat::from_path("map.svg"), Some(ImageFormat::Svg));
+        assert_eq!(ImageFormat::from_path("map.ppm"), Some(ImageFormat::Ppm));
+        assert_eq!(ImageFormat::from_path("map.txt"), None);
+        assert_eq!(ImageFormat::from_path("map"), None);
+    }
+
+    #[test]
+    fn test_write_ppm() {
+        let mut ppm = Vec::new();
+        image().write_ppm(&mut ppm).unwrap();
+
+        let header = b"P6\n6 4\n255\n";
+        assert!(ppm.starts_with(header));
+        assert_eq!(ppm.len(), header.len() + 6 * 4 * 3);
+        // second pixel of the first row is still the first cell, third one is the second cell
+        assert_eq!(
+            &ppm[header.len() + 3..header.len() + 9],
+            &[0, 0, 0, 255, 255, 255]
+        );
+        // third row of pixels is the second row of cells
+        assert_eq!(&ppm[header.len() + 36..header.len() + 39], &[40, 180, 40]);
+    }
+
+    #[test]
+    fn test_write_png() {
+        let mut png = Vec::new();
+        image().write_png(&mut png).unwrap();
+
+        assert!(png.starts_with(b"\x89PNG\r\n\x1a\n"));
+    }
+
+    #[test]
+    fn test_write_svg() {
+        let mut svg = Vec::new();
+        image().write_svg(&mut svg).unwrap();
+
+        let svg = String::from_utf8(svg).unwrap();
+        assert!(svg.starts_with("<svg "));
+        assert!(svg.trim_end().ends_with("</svg>"));
+        assert_eq!(svg.matches("<rect ").count(), 6);
+        assert!(svg.contains(r##"<rect x="4" y="2" width="2" height="2" fill="#f0c828"/>"##));
+    }
+}
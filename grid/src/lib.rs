@@ -0,0 +1,19 @@
+mod coordinate;
+mod direction;
+mod error;
+mod image;
+mod matrix;
+mod sparse;
+
+pub use self::coordinate::Coordinate;
+pub use self::coordinate::Distance;
+pub use self::direction::Direction;
+pub use self::error::GridError;
+pub use self::image::Colour;
+pub use self::image::Image;
+pub use self::image::ImageFormat;
+pub use self::matrix::Matrix;
+pub use self::matrix::MatrixView;
+pub use self::matrix::Pattern;
+pub use self::matrix::WordMatch;
+pub use self::sparse::SparseGrid;
@@ -0,0 +1,633 @@
+mod pattern;
+mod view;
+mod word_search;
+
+use std::ops::Index;
+use std::ops::IndexMut;
+use std::str::FromStr;
+
+pub use self::pattern::Pattern;
+pub use self::view::MatrixView;
+pub use self::word_search::WordMatch;
+use crate::Coordinate;
+use crate::Direction;
+use crate::Distance;
+use crate::GridError;
+
+/// A dense grid, stored row after row in a single vector.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Matrix<T> {
+    cells: Vec<T>,
+    height: usize,
+    width: usize,
+}
+
+impl<T> Matrix<T> {
+    /// Creates a matrix of the given size, computing each element from its coordinate.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the matrix would be empty.
+    pub fn from_fn<F>(height: usize, width: usize, mut f: F) -> Self
+    where
+        F: FnMut(Coordinate) -> T,
+    {
+        assert!(height > 0 && width > 0, "matrix must not be empty");
+
+        let cells = (0..height)
+            .flat_map(|row| (0..width).map(move |col| Coordinate::new(row, col)))
+            .map(&mut f)
+            .collect();
+
+        Matrix {
+            cells,
+            height,
+            width,
+        }
+    }
+
+    /// Parses a grid of characters, one row per line, converting each character with `f`.
+    pub fn parse_with<F>(s: &str, mut f: F) -> Result<Self, GridError>
+    where
+        F: FnMut(Coordinate, char) -> T,
+    {
+        let mut cells = Vec::with_capacity(s.len());
+        let mut height = 0;
+        let mut width = 0;
+
+        for (row, line) in s.lines().enumerate() {
+            height += 1;
+
+            let row_start = cells.len();
+            cells.extend(
+                line.chars()
+                    .enumerate()
+                    .map(|(col, elem)| f(Coordinate::new(row, col), elem)),
+            );
+            let line_width = cells.len() - row_start;
+
+            if row == 0 {
+                width = line_width;
+            } else if width != line_width {
+                return Err(GridError::UnequalRowsLength);
+            }
+        }
+
+        if width == 0 {
+            return Err(GridError::EmptyGrid);
+        }
+
+        Ok(Matrix {
+            cells,
+            height,
+            width,
+        })
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn is_on_grid(&self, coordinate: impl Into<Coordinate>) -> bool {
+        let coordinate = coordinate.into();
+        coordinate.row < self.height && coordinate.col < self.width
+    }
+
+    fn index_of(&self, coordinate: Coordinate) -> Option<usize> {
+        self.is_on_grid(coordinate)
+            .then(|| coordinate.row * self.width + coordinate.col)
+    }
+
+    pub fn get(&self, coordinate: impl Into<Coordinate>) -> Option<&T> {
+        self.index_of(coordinate.into())
+            .map(|index| &self.cells[index])
+    }
+
+    pub fn get_mut(&mut self, coordinate: impl Into<Coordinate>) -> Option<&mut T> {
+        self.index_of(coordinate.into())
+            .map(|index| &mut self.cells[index])
+    }
+
+    /// Replaces an element, returning the previous one, or `None` if the coordinate is not on
+    /// the matrix.
+    pub fn set(&mut self, coordinate: impl Into<Coordinate>, value: T) -> Option<T> {
+        self.get_mut(coordinate)
+            .map(|elem| std::mem::replace(elem, value))
+    }
+
+    /// Element at the given distance from a coordinate, if it is on the matrix.
+    pub fn get_offset(
+        &self,
+        coordinate: impl Into<Coordinate>,
+        distance: impl Into<Distance>,
+    ) -> Option<&T> {
+        self.get(coordinate.into().checked_add(distance)?)
+    }
+
+    /// Neighbour of a coordinate towards the given direction, if it is on the matrix.
+    pub fn step(
+        &self,
+        coordinate: impl Into<Coordinate>,
+        direction: Direction,
+    ) -> Option<Coordinate> {
+        coordinate
+            .into()
+            .next_towards(direction)
+            .filter(|&next| self.is_on_grid(next))
+    }
+
+    /// Iterates over the elements, row after row.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.cells.iter_mut()
+    }
+
+    pub fn iter_enumerate(&self) -> impl Iterator<Item = (Coordinate, &T)> {
+        let width = self.width;
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(index, elem)| (Coordinate::new(index / width, index % width), elem))
+    }
+
+    pub fn row(&self, row: usize) -> Option<&[T]> {
+        (row < self.height).then(|| &self.cells[row * self.width..(row + 1) * self.width])
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks_exact(self.width)
+    }
+
+    pub fn column(&self, col: usize) -> Option<impl Iterator<Item = &T>> {
+        (col < self.width).then(|| self.cells[col..].iter().step_by(self.width))
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|col| self.cells[col..].iter().step_by(self.width))
+    }
+
+    /// Rectangular part of the matrix, with `top_left` as its origin, if it fits in the matrix.
+    pub fn view(
+        &self,
+        top_left: impl Into<Coordinate>,
+        height: usize,
+        width: usize,
+    ) -> Option<MatrixView<'_, T>> {
+        MatrixView::new(self, top_left.into(), height, width)
+    }
+
+    /// Every view of the given size, sliding row after row over the matrix.
+    pub fn windows(&self, height: usize, width: usize) -> impl Iterator<Item = MatrixView<'_, T>> {
+        let rows = (self.height + 1).saturating_sub(height);
+        let cols = (self.width + 1).saturating_sub(width);
+
+        (0..rows)
+            .flat_map(move |row| (0..cols).map(move |col| Coordinate::new(row, col)))
+            .filter_map(move |top_left| self.view(top_left, height, width))
+    }
+
+    /// Creates a matrix of the same size, converting each element with `f`.
+    pub fn map<U, F>(&self, mut f: F) -> Matrix<U>
+    where
+        F: FnMut(&T) -> U,
+    {
+        Matrix {
+            cells: self.cells.iter().map(&mut f).collect(),
+            height: self.height,
+            width: self.width,
+        }
+    }
+}
+
+impl<T: Clone> Matrix<T> {
+    /// Swaps rows and columns.
+    pub fn transpose(&self) -> Self {
+        Matrix::from_fn(self.width, self.height, |coordinate| {
+            self[(coordinate.col, coordinate.row)].clone()
+        })
+    }
+
+    /// Rotates the matrix by 90 degrees clockwise.
+    pub fn rotate_90(&self) -> Self {
+        Matrix::from_fn(self.width, self.height, |coordinate| {
+            self[(self.height - 1 - coordinate.col, coordinate.row)].clone()
+        })
+    }
+
+    /// Mirrors the matrix left to right, reversing each row.
+    pub fn flip_horizontal(&self) -> Self {
+        Matrix::from_fn(self.height, self.width, |coordinate| {
+            self[(coordinate.row, self.width - 1 - coordinate.col)].clone()
+        })
+    }
+
+    /// Mirrors the matrix top to bottom, reversing the order of the rows.
+    pub fn flip_vertical(&self) -> Self {
+        Matrix::from_fn(self.height, self.width, |coordinate| {
+            self[(self.height - 1 - coordinate.row, coordinate.col)].clone()
+        })
+    }
+}
+
+impl<T> TryFrom<Vec<Vec<T>>> for Matrix<T> {
+    type Error = GridError;
+
+    fn try_from(matrix: Vec<Vec<T>>) -> Result<Self, Self::Error> {
+        let width = matrix
+            .first()
+            .map(Vec::len)
+            .filter(|&width| width > 0)
+            .ok_or(GridError::EmptyGrid)?;
+
+        if !matrix.iter().all(|row| row.len() == width) {
+            return Err(GridError::UnequalRowsLength);
+        }
+
+        Ok(Matrix {
+            height: matrix.len(),
+            width,
+            cells: matrix.into_iter().flatten().collect(),
+        })
+    }
+}
+
+impl FromStr for Matrix<char> {
+    type Err = GridError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Matrix::parse_with(s, |_, elem| elem)
+    }
+}
+
+impl<I, T> Index<I> for Matrix<T>
+where
+    I: Into<Coordinate>,
+{
+    type Output = T;
+
+    fn index(&self, coordinate: I) -> &Self::Output {
+        let coordinate = coordinate.into();
+        self.get(coordinate)
+            .unwrap_or_else(|| panic!("coordinate {coordinate} is out of bounds"))
+    }
+}
+
+impl<I, T> IndexMut<I> for Matrix<T>
+where
+    I: Into<Coordinate>,
+{
+    fn index_mut(&mut self, coordinate: I) -> &mut Self::Output {
+        let coordinate = coordinate.into();
+        self.get_mut(coordinate)
+            .unwrap_or_else(|| panic!("coordinate {coordinate} is out of bounds"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_tuple() {
+        let matrix = Matrix::try_from(vec![vec![1]]).unwrap();
+
+        assert_eq!(Some(&1), matrix.get((0, 0)));
+    }
+
+    #[test]
+    fn test_get_coordinate() {
+        let matrix = Matrix::try_from(vec![vec![1]]).unwrap();
+
+        assert_eq!(Some(&1), matrix.get(Coordinate::new(0, 0)));
+    }
+
+    #[test]
+    fn test_index_tuple() {
+        let matrix = Matrix::try_from(vec![vec![1]]).unwrap();
+
+        assert_eq!(&1, &matrix[(0, 0)]);
+    }
+
+    #[test]
+    fn test_index_coordinate() {
+        let matrix = Matrix::try_from(vec![vec![1]]).unwrap();
+
+        assert_eq!(&1, &matrix[Coordinate::new(0, 0)]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_index_out_of_bounds() {
+        let matrix = Matrix::try_from(vec![vec![1]]).unwrap();
+
+        let _ = matrix[(3, 3)];
+    }
+
+    #[test]
+    fn test_iter_enumerate() {
+        let matrix = Matrix::try_from(vec![vec![1, 2], vec![3, 4]]).unwrap();
+
+        assert_eq!(
+            vec![
+                (Coordinate::new(0, 0), &1),
+                (Coordinate::new(0, 1), &2),
+                (Coordinate::new(1, 0), &3),
+                (Coordinate::new(1, 1), &4),
+            ],
+            matrix.iter_enumerate().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_invalid_matrix() {
+        assert_eq!(
+            Err(GridError::UnequalRowsLength),
+            Matrix::try_from(vec![vec![1, 2], vec![3]])
+        );
+    }
+
+    #[test]
+    fn test_matrix_from_empty_vector() {
+        assert_eq!(
+            Err(GridError::EmptyGrid),
+            Matrix::<char>::try_from(vec![])
+        );
+    }
+
+    #[test]
+    fn test_matrix_from_empty_row() {
+        assert_eq!(
+            Err(GridError::EmptyGrid),
+            Matrix::<char>::try_from(vec![vec![]])
+        );
+    }
+
+    #[test]
+    fn test_matrix_dimensions() {
+        let matrix = Matrix::try_from(vec![vec![1, 2, 3], vec![4, 5, 6]]).unwrap();
+
+        assert_eq!(matrix.height(), 2);
+        assert_eq!(matrix.width(), 3);
+        assert!(matrix.is_on_grid((1, 2)));
+        assert!(!matrix.is_on_grid((2, 0)));
+        assert!(!matrix.is_on_grid((0, 3)));
+    }
+
+    #[test]
+    fn test_get_offset() {
+        let matrix = Matrix::try_from(vec![vec![1, 2], vec![3, 4]]).unwrap();
+
+        assert_eq!(matrix.get_offset((0, 0), (1, 1)), Some(&4));
+        assert_eq!(matrix.get_offset((1, 1), Distance::new(-1, 0)), Some(&2));
+        assert_eq!(matrix.get_offset((0, 0), (-1, 0)), None);
+        assert_eq!(matrix.get_offset((1, 1), (0, 1)), None);
+    }
+
+    #[test]
+    fn test_step() {
+        let matrix = Matrix::try_from(vec![vec![1, 2], vec![3, 4]]).unwrap();
+
+        assert_eq!(
+            matrix.step((0, 0), Direction::SouthEast),
+            Some(Coordinate::new(1, 1))
+        );
+        assert_eq!(matrix.step((0, 0), Direction::North), None);
+        assert_eq!(matrix.step((0, 1), Direction::East), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_index_out_of_bounds_column() {
+        let matrix = Matrix::try_from(vec![vec![1, 2], vec![3, 4]]).unwrap();
+
+        // would be the element (1, 0) if the column were not checked
+        let _ = matrix[(0, 2)];
+    }
+
+    #[test]
+    fn test_iter() {
+        let matrix = Matrix::try_from(vec![vec![1, 2], vec![3, 4]]).unwrap();
+
+        assert_eq!(matrix.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_rows() {
+        let matrix = Matrix::try_from(vec![vec![1, 2, 3], vec![4, 5, 6]]).unwrap();
+
+        assert_eq!(matrix.row(1), Some(&[4, 5, 6][..]));
+        assert_eq!(matrix.row(2), None);
+        assert_eq!(
+            matrix.rows().collect::<Vec<_>>(),
+            vec![&[1, 2, 3][..], &[4, 5, 6][..]]
+        );
+    }
+
+    #[test]
+    fn test_columns() {
+        let matrix = Matrix::try_from(vec![vec![1, 2, 3], vec![4, 5, 6]]).unwrap();
+
+        assert_eq!(
+            matrix.column(1).unwrap().copied().collect::<Vec<_>>(),
+            vec![2, 5]
+        );
+        assert!(matrix.column(3).is_none());
+        assert_eq!(
+            matrix
+                .columns()
+                .map(|column| column.copied().collect::<Vec<_>>())
+                .collect::<Vec<_>>(),
+            vec![vec![1, 4], vec![2, 5], vec![3, 6]]
+        );
+    }
+
+    #[test]
+    fn test_view() {
+        let matrix = Matrix::try_from(vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 9]]).unwrap();
+
+        let view = matrix.view((1, 1), 2, 2).unwrap();
+
+        assert_eq!(view.height(), 2);
+        assert_eq!(view.width(), 2);
+        assert_eq!(view[(0, 0)], 5);
+        assert_eq!(view.get((1, 1)), Some(&9));
+        assert_eq!(view.get((0, 2)), None);
+        assert!(matrix.view((1, 1), 3, 2).is_none());
+        assert!(matrix.view((0, 0), 0, 2).is_none());
+        assert!(matrix.view((usize::MAX, 0), 1, 1).is_none());
+        assert!(matrix.view((0, 1), 1, usize::MAX).is_none());
+    }
+
+    #[test]
+    fn test_windows() {
+        let matrix = Matrix::try_from(vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 9]]).unwrap();
+
+        let sums = matrix
+            .windows(2, 2)
+            .map(|window| window.iter_enumerate().map(|(_, elem)| elem).sum::<i32>())
+            .collect::<Vec<_>>();
+
+        assert_eq!(sums, vec![12, 16, 24, 28]);
+        assert_eq!(matrix.windows(3, 3).count(), 1);
+        assert_eq!(matrix.windows(4, 1).count(), 0);
+    }
+
+    #[test]
+    fn test_from_fn() {
+        let matrix = Matrix::from_fn(2, 3, |coordinate| coordinate.row * 10 + coordinate.col);
+
+        assert_eq!(
+            matrix,
+            Matrix::try_from(vec![vec![0, 1, 2], vec![10, 11, 12]]).unwrap()
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_from_fn_empty() {
+        let _ = Matrix::from_fn(0, 3, |_| 0);
+    }
+
+    #[test]
+    fn test_get_mut() {
+        let mut matrix = Matrix::try_from(vec![vec![1, 2], vec![3, 4]]).unwrap();
+
+        *matrix.get_mut((1, 0)).unwrap() = 5;
+
+        assert_eq!(matrix[(1, 0)], 5);
+        assert_eq!(matrix.get_mut((2, 0)), None);
+    }
+
+    #[test]
+    fn test_index_mut() {
+        let mut matrix = Matrix::try_from(vec![vec![1, 2], vec![3, 4]]).unwrap();
+
+        matrix[(0, 1)] += 10;
+
+        assert_eq!(matrix[(0, 1)], 12);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_index_mut_out_of_bounds() {
+        let mut matrix = Matrix::try_from(vec![vec![1]]).unwrap();
+
+        matrix[(0, 1)] = 2;
+    }
+
+    #[test]
+    fn test_set() {
+        let mut matrix = Matrix::try_from(vec![vec![1, 2], vec![3, 4]]).unwrap();
+
+        assert_eq!(matrix.set((1, 1), 8), Some(4));
+        assert_eq!(matrix.set((2, 1), 9), None);
+        assert_eq!(matrix[(1, 1)], 8);
+    }
+
+    #[test]
+    fn test_iter_mut() {
+        let mut matrix = Matrix::try_from(vec![vec![1, 2], vec![3, 4]]).unwrap();
+
+        matrix.iter_mut().for_each(|elem| *elem *= 2);
+
+        assert_eq!(matrix.iter().copied().collect::<Vec<_>>(), vec![2, 4, 6, 8]);
+    }
+
+    #[test]
+    fn test_map() {
+        let matrix = Matrix::try_from(vec![vec![1, 2], vec![3, 4]]).unwrap();
+
+        assert_eq!(
+            matrix.map(|elem| elem % 2 == 0),
+            Matrix::try_from(vec![vec![false, true], vec![false, true]]).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_transpose() {
+        let matrix = Matrix::try_from(vec![vec![1, 2, 3], vec![4, 5, 6]]).unwrap();
+
+        assert_eq!(
+            matrix.transpose(),
+            Matrix::try_from(vec![vec![1, 4], vec![2, 5], vec![3, 6]]).unwrap()
+        );
+        assert_eq!(matrix.transpose().transpose(), matrix);
+    }
+
+    #[test]
+    fn test_rotate_90() {
+        let matrix = Matrix::try_from(vec![vec![1, 2, 3], vec![4, 5, 6]]).unwrap();
+
+        assert_eq!(
+            matrix.rotate_90(),
+            Matrix::try_from(vec![vec![4, 1], vec![5, 2], vec![6, 3]]).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_rotate_90_four_times_is_identity() {
+        let matrix: Matrix<char> = "abc\ndef\nghi\njkl".parse().unwrap();
+
+        assert_ne!(matrix.rotate_90(), matrix);
+        assert_eq!(
+            matrix.rotate_90().rotate_90().rotate_90().rotate_90(),
+            matrix
+        );
+    }
+
+    #[test]
+    fn test_flips() {
+        let matrix = Matrix::try_from(vec![vec![1, 2, 3], vec![4, 5, 6]]).unwrap();
+
+        assert_eq!(
+            matrix.flip_horizontal(),
+            Matrix::try_from(vec![vec![3, 2, 1], vec![6, 5, 4]]).unwrap()
+        );
+        assert_eq!(
+            matrix.flip_vertical(),
+            Matrix::try_from(vec![vec![4, 5, 6], vec![1, 2, 3]]).unwrap()
+        );
+        assert_eq!(matrix.flip_horizontal().flip_horizontal(), matrix);
+        assert_eq!(matrix.flip_vertical().flip_vertical(), matrix);
+    }
+
+    #[test]
+    fn test_rotate_180_is_both_flips() {
+        let matrix: Matrix<char> = "abc\ndef".parse().unwrap();
+
+        assert_eq!(
+            matrix.rotate_90().rotate_90(),
+            matrix.flip_horizontal().flip_vertical()
+        );
+    }
+
+    #[test]
+    fn test_matrix_from_str() {
+        let matrix: Matrix<char> = "ab\ncd\n".parse().unwrap();
+
+        assert_eq!(matrix[(0, 1)], 'b');
+        assert_eq!(matrix[(1, 0)], 'c');
+        assert_eq!(
+            "ab\nc".parse::<Matrix<char>>(),
+            Err(GridError::UnequalRowsLength)
+        );
+        assert_eq!("".parse::<Matrix<char>>(), Err(GridError::EmptyGrid));
+    }
+
+    #[test]
+    fn test_matrix_parse_with() {
+        let matrix = Matrix::parse_with("12\n34", |coordinate, elem| {
+            (coordinate, elem.to_digit(10).unwrap())
+        })
+        .unwrap();
+
+        assert_eq!(matrix[(1, 0)], (Coordinate::new(1, 0), 3));
+    }
+}
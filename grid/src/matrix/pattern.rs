@@ -0,0 +1,204 @@
+use std::str::FromStr;
+
+use crate::Coordinate;
+use crate::GridError;
+use crate::Matrix;
+use crate::MatrixView;
+
+/// A rectangular shape of characters to look for in a [`Matrix`], where `.` matches anything.
+///
+/// A pattern may also match in some of its rotated or reflected forms, see
+/// [`Pattern::with_rotations`] and [`Pattern::with_reflections`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pattern {
+    variants: Vec<Matrix<Option<char>>>,
+}
+
+impl Pattern {
+    /// Character matching any element of the matrix.
+    pub const WILDCARD: char = '.';
+
+    /// Also matches the pattern rotated by 90, 180 and 270 degrees.
+    pub fn with_rotations(mut self) -> Self {
+        let rotated = self
+            .variants
+            .iter()
+            .flat_map(|variant| {
+                std::iter::successors(Some(variant.rotate_90()), |rotated| {
+                    Some(rotated.rotate_90())
+                })
+                .take(3)
+            })
+            .collect::<Vec<_>>();
+
+        self.extend(rotated);
+        self
+    }
+
+    /// Also matches the pattern mirrored left to right.
+    ///
+    /// Combined with [`Pattern::with_rotations`], this covers every symmetry of the pattern.
+    pub fn with_reflections(mut self) -> Self {
+        let reflected = self
+            .variants
+            .iter()
+            .map(Matrix::flip_horizontal)
+            .collect::<Vec<_>>();
+
+        self.extend(reflected);
+        self
+    }
+
+    fn extend(&mut self, variants: Vec<Matrix<Option<char>>>) {
+        for variant in variants {
+            if !self.variants.contains(&variant) {
+                self.variants.push(variant);
+            }
+        }
+    }
+
+    /// Number of distinct forms the pattern matches in.
+    pub fn variants_count(&self) -> usize {
+        self.variants.len()
+    }
+
+    /// Checks whether any form of the pattern matches with its top left corner at `anchor`.
+    pub fn matches_at(&self, matrix: &Matrix<char>, anchor: impl Into<Coordinate>) -> bool {
+        let anchor = anchor.into();
+
+        self.variants.iter().any(|variant| {
+            matrix
+                .view(anchor, variant.height(), variant.width())
+                .is_some_and(|view| variant_matches(variant, view))
+        })
+    }
+}
+
+fn variant_matches(variant: &Matrix<Option<char>>, view: MatrixView<'_, char>) -> bool {
+    variant
+        .iter_enumerate()
+        .all(|(coordinate, expected)| expected.is_none_or(|expected| view[coordinate] == expected))
+}
+
+impl FromStr for Pattern {
+    type Err = GridError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let pattern = Matrix::parse_with(s, |_, elem| (elem != Pattern::WILDCARD).then_some(elem))?;
+
+        Ok(Pattern {
+            variants: vec![pattern],
+        })
+    }
+}
+
+impl Matrix<char> {
+    /// Finds every anchor, the top left corner of the match, where the pattern matches.
+    ///
+    /// Matches may overlap. Anchors are returned once each, row after row, even when several
+    /// forms of the pattern match there.
+    pub fn find_pattern(&self, pattern: &Pattern) -> Vec<Coordinate> {
+        self.iter_enumerate()
+            .map(|(anchor, _)| anchor)
+            .filter(|&anchor| pattern.matches_at(self, anchor))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matrix(s: &str) -> Matrix<char> {
+        s.parse().unwrap()
+    }
+
+    fn pattern(s: &str) -> Pattern {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_find_pattern_with_wildcards() {
+        let matrix = matrix("ABAB\nxyzy\nABAB");
+
+        assert_eq!(
+            matrix.find_pattern(&pattern("A.A\n...\nA.A")),
+            vec![Coordinate::new(0, 0)]
+        );
+        assert_eq!(
+            matrix.find_pattern(&pattern("B\ny")),
+            vec![Coordinate::new(0, 1), Coordinate::new(0, 3)]
+        );
+    }
+
+    #[test]
+    fn test_find_pattern_overlapping() {
+        let matrix = matrix("AAAA");
+
+        assert_eq!(matrix.find_pattern(&pattern("AA")).len(), 3);
+    }
+
+    #[test]
+    fn test_find_pattern_at_border() {
+        let matrix = matrix("MS\nA.\nMS");
+
+        assert!(matrix.find_pattern(&pattern("M.S\n.A.\nM.S")).is_empty());
+    }
+
+    #[test]
+    fn test_pattern_with_rotations() {
+        let matrix = matrix("AB\n..\nBA");
+        let pattern = pattern("AB");
+
+        assert_eq!(matrix.find_pattern(&pattern), vec![Coordinate::new(0, 0)]);
+
+        let rotated = pattern.with_rotations();
+        assert_eq!(rotated.variants_count(), 4);
+        assert_eq!(
+            matrix.find_pattern(&rotated),
+            vec![Coordinate::new(0, 0), Coordinate::new(2, 0)]
+        );
+    }
+
+    #[test]
+    fn test_pattern_with_reflections() {
+        let matrix = matrix("ABC\nCBA");
+        let pattern = pattern("ABC");
+
+        assert_eq!(matrix.find_pattern(&pattern), vec![Coordinate::new(0, 0)]);
+        assert_eq!(
+            matrix.find_pattern(&pattern.with_reflections()),
+            vec![Coordinate::new(0, 0), Coordinate::new(1, 0)]
+        );
+    }
+
+    #[test]
+    fn test_pattern_variants_are_distinct() {
+        assert_eq!(
+            pattern("A.A\n.A.\nA.A").with_rotations().variants_count(),
+            1
+        );
+        assert_eq!(
+            pattern("M.S\n.A.\nM.S")
+                .with_rotations()
+                .with_reflections()
+                .variants_count(),
+            4
+        );
+        assert_eq!(
+            pattern("AB.\n.C.")
+                .with_rotations()
+                .with_reflections()
+                .variants_count(),
+            8
+        );
+    }
+
+    #[test]
+    fn test_parse_pattern_not_rectangular() {
+        assert_eq!(
+            "M.\n.".parse::<Pattern>(),
+            Err(GridError::UnequalRowsLength)
+        );
+    }
+}
@@ -0,0 +1,150 @@
+use std::ops::Index;
+
+use crate::Coordinate;
+use crate::Matrix;
+
+/// A rectangular part of a [`Matrix`], addressed by coordinates relative to its top left corner.
+#[derive(Debug, Clone, Copy)]
+pub struct MatrixView<'a, T> {
+    matrix: &'a Matrix<T>,
+    origin: Coordinate,
+    height: usize,
+    width: usize,
+}
+
+impl<'a, T> MatrixView<'a, T> {
+    pub(super) fn new(
+        matrix: &'a Matrix<T>,
+        origin: Coordinate,
+        height: usize,
+        width: usize,
+    ) -> Option<Self> {
+        let fits = height > 0
+            && width > 0
+            && origin
+                .row
+                .checked_add(height)
+                .is_some_and(|end| end <= matrix.height())
+            && origin
+                .col
+                .checked_add(width)
+                .is_some_and(|end| end <= matrix.width());
+
+        fits.then_some(MatrixView {
+            matrix,
+            origin,
+            height,
+            width,
+        })
+    }
+
+    /// Coordinate of the top left corner of the view in the matrix.
+    pub fn origin(&self) -> Coordinate {
+        self.origin
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn is_on_grid(&self, coordinate: impl Into<Coordinate>) -> bool {
+        let coordinate = coordinate.into();
+        coordinate.row < self.height && coordinate.col < self.width
+    }
+
+    pub fn get(&self, coordinate: impl Into<Coordinate>) -> Option<&'a T> {
+        let coordinate = coordinate.into();
+        self.is_on_grid(coordinate)
+            .then(|| {
+                self.matrix.get(Coordinate::new(
+                    self.origin.row + coordinate.row,
+                    self.origin.col + coordinate.col,
+                ))
+            })
+            .flatten()
+    }
+
+    pub fn row(&self, row: usize) -> Option<&'a [T]> {
+        (row < self.height)
+            .then(|| self.matrix.row(self.origin.row + row))
+            .flatten()
+            .map(|row| &row[self.origin.col..self.origin.col + self.width])
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &'a [T]> + '_ {
+        (0..self.height).filter_map(|row| self.row(row))
+    }
+
+    /// Iterates over the elements, row after row, with coordinates relative to the view.
+    pub fn iter_enumerate(&self) -> impl Iterator<Item = (Coordinate, &'a T)> + '_ {
+        self.rows().enumerate().flat_map(|(row_idx, row)| {
+            row.iter()
+                .enumerate()
+                .map(move |(col_idx, elem)| (Coordinate::new(row_idx, col_idx), elem))
+        })
+    }
+}
+
+impl<I, T> Index<I> for MatrixView<'_, T>
+where
+    I: Into<Coordinate>,
+{
+    type Output = T;
+
+    fn index(&self, coordinate: I) -> &Self::Output {
+        let coordinate = coordinate.into();
+        self.get(coordinate)
+            .unwrap_or_else(|| panic!("coordinate {coordinate} is out of bounds"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matrix() -> Matrix<char> {
+        "abcd\nefgh\nijkl".parse().unwrap()
+    }
+
+    #[test]
+    fn test_view_rows() {
+        let matrix = matrix();
+        let view = matrix.view((1, 1), 2, 3).unwrap();
+
+        assert_eq!(view.origin(), Coordinate::new(1, 1));
+        assert_eq!(view.row(0), Some(&['f', 'g', 'h'][..]));
+        assert_eq!(view.row(1), Some(&['j', 'k', 'l'][..]));
+        assert_eq!(view.row(2), None);
+        assert_eq!(view.rows().count(), 2);
+    }
+
+    #[test]
+    fn test_view_iter_enumerate() {
+        let matrix = matrix();
+        let view = matrix.view((0, 2), 2, 2).unwrap();
+
+        assert_eq!(
+            view.iter_enumerate().collect::<Vec<_>>(),
+            vec![
+                (Coordinate::new(0, 0), &'c'),
+                (Coordinate::new(0, 1), &'d'),
+                (Coordinate::new(1, 0), &'g'),
+                (Coordinate::new(1, 1), &'h'),
+            ]
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_view_index_out_of_bounds() {
+        let matrix = matrix();
+        let view = matrix.view((0, 0), 2, 2).unwrap();
+
+        // on the matrix, but not on the view
+        let _ = view[(0, 2)];
+    }
+}
@@ -0,0 +1,158 @@
+use crate::Coordinate;
+use crate::Direction;
+use crate::Matrix;
+
+/// A word found in a [`Matrix`] of characters.
+#[derive(Debug, Clone, Copy, Hash, Eq, PartialEq)]
+pub struct WordMatch<'w> {
+    /// Coordinate of the first letter of the word.
+    pub start: Coordinate,
+    /// Direction the word is read towards.
+    pub direction: Direction,
+    pub word: &'w str,
+}
+
+impl Matrix<char> {
+    /// Finds every occurrence of the given words read towards any of the given directions.
+    ///
+    /// Matches may overlap and share letters with each other. They are returned ordered by
+    /// starting coordinate, then by the order of the words and directions given.
+    pub fn find_words<'w>(
+        &self,
+        words: &[&'w str],
+        directions: &[Direction],
+    ) -> Vec<WordMatch<'w>> {
+        self.iter_enumerate()
+            .flat_map(|(start, &letter)| {
+                words
+                    .iter()
+                    .filter(move |word| word.starts_with(letter))
+                    .flat_map(move |&word| {
+                        directions
+                            .iter()
+                            .filter(move |&&direction| self.word_is_at(word, start, direction))
+                            .map(move |&direction| WordMatch {
+                                start,
+                                direction,
+                                word,
+                            })
+                    })
+            })
+            .collect()
+    }
+
+    /// Checks whether the word can be read from `start` towards the given direction.
+    pub fn word_is_at(&self, word: &str, start: Coordinate, direction: Direction) -> bool {
+        let mut position = Some(start);
+
+        word.chars().all(|letter| {
+            let Some(current) = position else {
+                return false;
+            };
+            position = self.step(current, direction);
+            self.get(current) == Some(&letter)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matrix(s: &str) -> Matrix<char> {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_word_is_at() {
+        let matrix = matrix("OOOS\nOOAO\nOMOO\nXOOO");
+
+        assert!(matrix.word_is_at("XMAS", Coordinate::new(3, 0), Direction::NorthEast));
+        assert!(!matrix.word_is_at("XMAS", Coordinate::new(3, 0), Direction::North));
+        assert!(matrix.word_is_at("", Coordinate::new(3, 0), Direction::North));
+    }
+
+    #[test]
+    fn test_word_is_at_border() {
+        let matrix = matrix("SOOO\nAOOO\nMOOO\nOXOO");
+
+        assert!(!matrix.word_is_at("XMAS", Coordinate::new(3, 1), Direction::NorthWest));
+    }
+
+    #[test]
+    fn test_find_words() {
+        let matrix = matrix("CAT\nOXA\nWOT");
+
+        assert_eq!(
+            matrix.find_words(&["CAT", "COW", "TAT"], &Direction::ALL),
+            vec![
+                WordMatch {
+                    start: Coordinate::new(0, 0),
+                    direction: Direction::East,
+                    word: "CAT",
+                },
+                WordMatch {
+                    start: Coordinate::new(0, 0),
+                    direction: Direction::South,
+                    word: "COW",
+                },
+                WordMatch {
+                    start: Coordinate::new(0, 2),
+                    direction: Direction::South,
+                    word: "TAT",
+                },
+                WordMatch {
+                    start: Coordinate::new(2, 2),
+                    direction: Direction::North,
+                    word: "TAT",
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_find_words_with_restricted_directions() {
+        let matrix = matrix("AB.\n.AB\n..A");
+
+        let orthogonal = matrix.find_words(&["AB"], &Direction::ORTHOGONAL);
+        assert_eq!(
+            orthogonal
+                .iter()
+                .map(|found| (found.start, found.direction))
+                .collect::<Vec<_>>(),
+            vec![
+                (Coordinate::new(0, 0), Direction::East),
+                (Coordinate::new(1, 1), Direction::North),
+                (Coordinate::new(1, 1), Direction::East),
+                (Coordinate::new(2, 2), Direction::North),
+            ]
+        );
+
+        let diagonal = matrix.find_words(&["AA"], &Direction::DIAGONAL);
+        assert_eq!(diagonal.len(), 4);
+        assert!(matrix
+            .find_words(&["AA"], &Direction::ORTHOGONAL)
+            .is_empty());
+    }
+
+    #[test]
+    fn test_find_overlapping_words() {
+        let matrix = matrix("ABABA");
+
+        let found = matrix.find_words(&["ABA"], &[Direction::East]);
+
+        assert_eq!(
+            found.iter().map(|found| found.start).collect::<Vec<_>>(),
+            vec![Coordinate::new(0, 0), Coordinate::new(0, 2)]
+        );
+    }
+
+    #[test]
+    fn test_find_words_sharing_letters() {
+        let matrix = matrix("XMASAMX");
+
+        let found = matrix.find_words(&["XMAS", "SAMX"], &[Direction::East, Direction::West]);
+
+        assert_eq!(found.len(), 4);
+    }
+}
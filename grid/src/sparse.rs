@@ -0,0 +1,135 @@
+use std::collections::HashMap;
+
+use crate::Coordinate;
+use crate::Direction;
+
+/// A grid that only stores its occupied cells.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Coordinate, T>,
+    height: usize,
+    width: usize,
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new(height: usize, width: usize) -> Self {
+        SparseGrid {
+            cells: HashMap::new(),
+            height,
+            width,
+        }
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn is_on_grid(&self, coordinate: impl Into<Coordinate>) -> bool {
+        let coordinate = coordinate.into();
+        coordinate.row < self.height && coordinate.col < self.width
+    }
+
+    /// Neighbour of a coordinate towards the given direction, if it is on the grid.
+    pub fn step(
+        &self,
+        coordinate: impl Into<Coordinate>,
+        direction: Direction,
+    ) -> Option<Coordinate> {
+        coordinate
+            .into()
+            .next_towards(direction)
+            .filter(|&next| self.is_on_grid(next))
+    }
+
+    pub fn get(&self, coordinate: impl Into<Coordinate>) -> Option<&T> {
+        self.cells.get(&coordinate.into())
+    }
+
+    pub fn contains(&self, coordinate: impl Into<Coordinate>) -> bool {
+        self.cells.contains_key(&coordinate.into())
+    }
+
+    /// Stores a cell, returning the one previously stored at the same coordinate.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the coordinate is not on the grid.
+    pub fn insert(&mut self, coordinate: impl Into<Coordinate>, cell: T) -> Option<T> {
+        let coordinate = coordinate.into();
+        assert!(
+            self.is_on_grid(coordinate),
+            "coordinate {coordinate} is not on the grid"
+        );
+        self.cells.insert(coordinate, cell)
+    }
+
+    pub fn remove(&mut self, coordinate: impl Into<Coordinate>) -> Option<T> {
+        self.cells.remove(&coordinate.into())
+    }
+
+    /// Number of occupied cells.
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Iterates over the occupied cells, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (Coordinate, &T)> {
+        self.cells
+            .iter()
+            .map(|(&coordinate, cell)| (coordinate, cell))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_on_grid() {
+        let grid = SparseGrid::<()>::new(2, 3);
+
+        assert!(grid.is_on_grid((1, 2)));
+        assert!(!grid.is_on_grid((2, 0)));
+        assert!(!grid.is_on_grid((0, 3)));
+    }
+
+    #[test]
+    fn test_step() {
+        let grid = SparseGrid::<()>::new(2, 3);
+
+        assert_eq!(
+            grid.step((0, 0), Direction::East),
+            Some(Coordinate::new(0, 1))
+        );
+        assert_eq!(grid.step((0, 0), Direction::North), None);
+        assert_eq!(grid.step((1, 2), Direction::South), None);
+        assert_eq!(grid.step((1, 2), Direction::East), None);
+    }
+
+    #[test]
+    fn test_insert_and_remove() {
+        let mut grid = SparseGrid::new(2, 2);
+
+        assert_eq!(grid.insert((1, 1), 'a'), None);
+        assert_eq!(grid.insert((1, 1), 'b'), Some('a'));
+        assert_eq!(grid.get((1, 1)), Some(&'b'));
+        assert_eq!(grid.remove((1, 1)), Some('b'));
+        assert_eq!(grid.get((1, 1)), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_insert_out_of_grid() {
+        let mut grid = SparseGrid::new(2, 2);
+
+        grid.insert((2, 2), ());
+    }
+}